@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::FinalizeError,
+    factor::{Factor, Finalizable, SubPoly},
+    polynomial::Polynomial,
+    power::power_value,
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    traits::{PolyTypes, PolyValues},
+};
+
+// Polynomial prepared for `Polynomial::evaluate_batch`.
+// Variables of the bound symbols are resolved to their index in a binding, so each binding is substituted
+// by indexing instead of rebuilding the tree, and every subtree that does not depend on them is evaluated
+// once, when the plan is built. Products and sums are done in the same order as `Polynomial::as_value` does them.
+pub struct BatchPlan<Values> {
+    root: Node<Values>,
+}
+
+enum Node<Values> {
+    // value (or error) of a subtree that does not depend on the binding
+    Invariant(Result<Values, FinalizeError>),
+    // index of the bound value
+    Bound(usize),
+    Sum(Vec<Node<Values>>),
+    Product {
+        coefficient: Values,
+        factors: Vec<Node<Values>>,
+    },
+    // power of a factor other than one, zero power takes the unit of the type of the bound factor
+    Power(Box<Node<Values>>, u64),
+}
+
+impl<Values> BatchPlan<Values> {
+    pub fn new<Types>(polynomial: &Polynomial<Values, Types>, symbols: &[Symbol<Types>]) -> Self
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let mut indices = HashMap::new();
+        for (index, symbol) in symbols.iter().enumerate() {
+            // as with substitution one by one, only the first value of a repeated symbol is used
+            indices.entry(symbol.clone()).or_insert(index);
+        }
+        let folded = polynomial.clone().fold_constants();
+        let typed = symbols
+            .iter()
+            .all(|symbol| symbol.associated_type.is_some());
+        // bound values are checked against the types of their symbols, so the tree is typed only once
+        let root = match folded.as_type() {
+            Err(err) if typed => Node::Invariant(Err(err)),
+            _ => Planner { indices, typed }.sub_poly(folded.sub_poly()),
+        };
+        BatchPlan { root }
+    }
+}
+
+impl<Values> BatchPlan<Values> {
+    pub fn evaluate<Types>(&self, values: &[Values]) -> Result<Values, FinalizeError>
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        self.root.evaluate::<Types>(values)
+    }
+}
+
+impl<Values> Node<Values> {
+    fn evaluate<Types>(&self, values: &[Values]) -> Result<Values, FinalizeError>
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        match self {
+            Node::Invariant(result) => result.clone(),
+            Node::Bound(index) => Ok(values[*index].clone()),
+            Node::Sum(parts) => parts
+                .iter()
+                .try_fold(None, |sum: Option<Values>, part| {
+                    let part_value = part.evaluate::<Types>(values)?;
+                    Ok(Some(match sum {
+                        None => part_value,
+                        Some(sum) => (sum + part_value).ok_or(FinalizeError::NoValueToFinalize)?,
+                    }))
+                })?
+                .ok_or(FinalizeError::NoTypeToFinalize),
+            Node::Product {
+                coefficient,
+                factors,
+            } => {
+                let monomial_value =
+                    factors
+                        .iter()
+                        .try_fold(None, |product: Option<Values>, factor| {
+                            let factor_value = factor.evaluate::<Types>(values)?;
+                            Ok(Some(match product {
+                                None => factor_value,
+                                Some(product) => (product * factor_value)
+                                    .ok_or(FinalizeError::NoValueToFinalize)?,
+                            }))
+                        })?;
+                match monomial_value {
+                    Some(monomial_value) => (coefficient.clone() * monomial_value)
+                        .ok_or(FinalizeError::NoValueToFinalize),
+                    None => Ok(coefficient.clone()),
+                }
+            }
+            Node::Power(base, power) => {
+                let base_value = base.evaluate::<Types>(values)?;
+                let base_type = base_value.as_type();
+                if *power == 0 {
+                    return Values::one_with_type(base_type)
+                        .ok_or(FinalizeError::NoValueToFinalize);
+                }
+                power_value(base_value, *power, base_type.power_strategy())
+            }
+        }
+    }
+
+    fn is_invariant(&self) -> bool {
+        matches!(self, Node::Invariant(_))
+    }
+
+    // Nodes that depend only on invariant ones are evaluated right away.
+    fn hoisted<Types>(self) -> Self
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let invariant = match &self {
+            Node::Invariant(_) | Node::Bound(_) => return self,
+            Node::Sum(parts) => parts.iter().all(Node::is_invariant),
+            Node::Product { factors, .. } => factors.iter().all(Node::is_invariant),
+            Node::Power(base, _) => base.is_invariant(),
+        };
+        if invariant {
+            Node::Invariant(self.evaluate::<Types>(&[]))
+        } else {
+            self
+        }
+    }
+}
+
+struct Planner<Types> {
+    indices: HashMap<Symbol<Types>, usize>,
+    // whether types of all nodes are already known (see `BatchPlan::new`)
+    typed: bool,
+}
+
+impl<Types> Planner<Types>
+where
+    Types: PolyTypes<Types>,
+{
+    fn sub_poly<Values>(&self, poly: &SubPoly<Values, Types>) -> Node<Values>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        Node::Sum(poly.terms().iter().map(|term| self.term(term)).collect()).hoisted::<Types>()
+    }
+
+    fn term<Values>(&self, term: &Term<Values, Types>) -> Node<Values>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        Node::Product {
+            coefficient: term.coefficient().clone(),
+            factors: term
+                .factors()
+                .iter()
+                .map(|factor| self.monomial_factor(factor))
+                .collect(),
+        }
+        .hoisted::<Types>()
+    }
+
+    fn monomial_factor<Values>(&self, factor: &MonomialFactor<Values, Types>) -> Node<Values>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        match factor.power() {
+            1 => self.factor(factor.factor()),
+            // the unit depends only on the type, which is already known for typed plans
+            0 if self.typed => Node::Invariant(factor.finalize_type().and_then(|factor_type| {
+                Values::one_with_type(factor_type).ok_or(FinalizeError::NoValueToFinalize)
+            })),
+            power => Node::Power(Box::new(self.factor(factor.factor())), power).hoisted::<Types>(),
+        }
+    }
+
+    fn factor<Values>(&self, factor: &Factor<Values, Types>) -> Node<Values>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        match factor {
+            Factor::Value(value) => Node::Invariant(Ok(value.value().clone())),
+            Factor::Variable(variable) => match self.indices.get(variable.symbol()) {
+                Some(index) => Node::Bound(*index),
+                None => Node::Invariant(Err(FinalizeError::NoValueToFinalize)),
+            },
+            Factor::SubPoly(poly) => self.sub_poly(poly),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{
+    error::FinalizeError,
+    factor::{Factor, Finalizable, SubPoly},
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    traits::{CasSyntax, PolyTypes, PolyValues},
+};
+
+// Products are written with the commutative operator of the syntax only when `MulTraits` says so,
+// products of untyped parts are assumed to be non-commutative.
+// Non-commutative operators bind tighter than `*` in Maxima and Mathematica,
+// so a product that already contains `*` is parenthesised before it is multiplied non-commutatively.
+pub struct CasWriter {
+    syntax: CasSyntax,
+}
+
+impl CasWriter {
+    pub fn new(syntax: CasSyntax) -> Self {
+        CasWriter { syntax }
+    }
+
+    // Python snippet that declares every symbol and assigns the polynomial to `expr`.
+    // Symbols are commutative only if their type commutes with every type used in the polynomial.
+    pub fn sympy_script<Values, Types>(&self, poly: &SubPoly<Values, Types>) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let mut symbols = BTreeSet::new();
+        let mut types = vec![];
+        collect(poly, &mut symbols, &mut types);
+
+        let mut output = String::from("from sympy import *\n");
+        for symbol in symbols {
+            let commutative = symbol.associated_type.as_ref().is_some_and(|symbol_type| {
+                types.iter().all(|other| {
+                    is_commutative(Ok(symbol_type.clone()), Ok(other.clone()))
+                        && is_commutative(Ok(other.clone()), Ok(symbol_type.clone()))
+                })
+            });
+            match commutative {
+                true => writeln!(output, "{0} = Symbol('{0}')", symbol.label),
+                false => writeln!(
+                    output,
+                    "{0} = Symbol('{0}', commutative=False)",
+                    symbol.label
+                ),
+            }
+            .unwrap();
+        }
+        writeln!(output, "expr = {}", self.sub_poly(poly)).unwrap();
+        output
+    }
+
+    pub fn sub_poly<Values, Types>(&self, poly: &SubPoly<Values, Types>) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        if poly.terms().is_empty() {
+            return "0".to_owned();
+        }
+        poly.terms()
+            .iter()
+            .map(|term| self.term(term))
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    fn term<Values, Types>(&self, term: &Term<Values, Types>) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let mut output = String::new();
+        let mut product_type = Ok(term.coefficient().as_type());
+        // whether the product written so far is joined with `*` at its top level
+        let mut commutative_product = false;
+        // unit coefficients are omitted unless there is nothing else in the term
+        if !term.coefficient().is_one() || term.factors().is_empty() {
+            output.push_str(&term.coefficient().to_cas(self.syntax));
+        }
+        for factor in term.factors() {
+            let factor_type = factor.finalize_type();
+            if !output.is_empty() {
+                let commutative = is_commutative(product_type.clone(), factor_type.clone());
+                if !commutative && commutative_product && self.syntax != CasSyntax::SymPy {
+                    output = format!("({})", output);
+                }
+                commutative_product = commutative;
+                output.push_str(self.mul_operator(commutative));
+            }
+            output.push_str(&self.monomial_factor(factor));
+            product_type = product_type.and_then(|product_type| {
+                (product_type * factor_type?)
+                    .result
+                    .ok_or(FinalizeError::NoTypeToFinalize)
+            });
+        }
+        output
+    }
+
+    fn monomial_factor<Values, Types>(&self, factor: &MonomialFactor<Values, Types>) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let base = match factor.factor() {
+            Factor::Value(value) if factor.power() != 1 => {
+                format!("({})", value.value().to_cas(self.syntax))
+            }
+            Factor::Value(value) => value.value().to_cas(self.syntax),
+            Factor::Variable(variable) => variable.symbol().label.clone(),
+            Factor::SubPoly(poly) => format!("({})", self.sub_poly(poly)),
+        };
+        let base_type = factor.factor().finalize_type();
+        let commutative = is_commutative(base_type.clone(), base_type);
+        match (self.syntax, factor.power(), commutative) {
+            (_, 1, _) => base,
+            (CasSyntax::SymPy, power, _) => format!("{}**{}", base, power),
+            (CasSyntax::Maxima, power, true) => format!("{}^{}", base, power),
+            (CasSyntax::Maxima, power, false) => format!("{}^^{}", base, power),
+            (CasSyntax::Mathematica, power, true) | (CasSyntax::Mathematica, power @ 0, false) => {
+                format!("{}^{}", base, power)
+            }
+            // there is no non-commutative power in Mathematica
+            (CasSyntax::Mathematica, power, false) => {
+                format!("({})", vec![base; power as usize].join("**"))
+            }
+        }
+    }
+
+    fn mul_operator(&self, commutative: bool) -> &'static str {
+        match (self.syntax, commutative) {
+            (CasSyntax::SymPy, _) | (_, true) => "*",
+            (CasSyntax::Maxima, false) => " . ",
+            (CasSyntax::Mathematica, false) => "**",
+        }
+    }
+}
+
+fn is_commutative<Types>(
+    lhs: Result<Types, FinalizeError>,
+    rhs: Result<Types, FinalizeError>,
+) -> bool
+where
+    Types: PolyTypes<Types>,
+{
+    match (lhs, rhs) {
+        (Ok(lhs), Ok(rhs)) => (lhs * rhs).commutative,
+        _ => false,
+    }
+}
+
+// Symbols and types of all values and typed symbols of the polynomial.
+fn collect<Values, Types>(
+    poly: &SubPoly<Values, Types>,
+    symbols: &mut BTreeSet<Symbol<Types>>,
+    types: &mut Vec<Types>,
+) where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let add_type = |value_type: Types, types: &mut Vec<Types>| {
+        if !types.contains(&value_type) {
+            types.push(value_type);
+        }
+    };
+    for term in poly.terms() {
+        add_type(term.coefficient().as_type(), types);
+        for factor in term.factors() {
+            match factor.factor() {
+                Factor::Value(value) => add_type(value.value().as_type(), types),
+                Factor::Variable(variable) => {
+                    if let Some(symbol_type) = &variable.symbol().associated_type {
+                        add_type(symbol_type.clone(), types);
+                    }
+                    symbols.insert(variable.symbol().clone());
+                }
+                Factor::SubPoly(poly) => collect(poly, symbols, types),
+            }
+        }
+    }
+}
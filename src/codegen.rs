@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use crate::{
+    error::CodegenError,
+    lowering::{lower, lower_horner, Op},
+    polynomial::Polynomial,
+    symbol::Symbol,
+    traits::{PolyTypes, PolyValues},
+};
+
+/// Describes how values of a domain are written in generated Rust code.
+///
+/// Operands passed to `mul` and `add` are names of local variables that can be used again later,
+/// so the produced expressions must not move them (for example `&lhs * &rhs` for non-`Copy` values).
+pub trait ValueCodegen<Values, Types> {
+    /// Rust type of values of `value_type`, used for arguments, temporaries and the result.
+    fn rust_type(&self, value_type: &Types) -> String;
+
+    /// Rust expression that constructs `value`.
+    fn constant(&self, value: &Values) -> String;
+
+    /// Rust expression for `lhs * rhs`.
+    fn mul(&self, lhs: &str, lhs_type: &Types, rhs: &str, rhs_type: &Types) -> String;
+
+    /// Rust expression for `lhs + rhs`.
+    fn add(&self, lhs: &str, lhs_type: &Types, rhs: &str, rhs_type: &Types) -> String;
+}
+
+/// Describes how values of a scalar domain are written in generated C code.
+pub trait CValueCodegen<Values> {
+    /// C type of the values (for example `double`).
+    fn c_type(&self) -> String;
+
+    /// C expression of `value`.
+    fn constant(&self, value: &Values) -> String;
+}
+
+/// Result of `Polynomial::to_c`.
+pub struct CSource {
+    /// Declaration of the function together with the order of its arguments.
+    pub header: String,
+    /// C99 definition of the function, it does not depend on any headers.
+    pub source: String,
+}
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Rust source of `pub fn name(...)` that evaluates the polynomial,
+    /// `arguments` become its parameters (named by labels, so those have to be valid identifiers).
+    /// Labels with uppercase letters (like matrices `A`) are kept as they are and `non_snake_case` is allowed for the function.
+    ///
+    /// Constant parts are evaluated during generation, powers are expanded into multiplications
+    /// the same way `as_value` computes them, and common subexpressions are computed once.
+    pub fn to_rust_fn<Codegen>(
+        &self,
+        name: &str,
+        arguments: &[Symbol<Types>],
+        codegen: &Codegen,
+    ) -> Result<String, CodegenError>
+    where
+        Codegen: ValueCodegen<Values, Types>,
+    {
+        let lowered = lower(self, arguments, |value| codegen.constant(value))?;
+        let uses = lowered.uses();
+        let names = (0..lowered.instructions.len())
+            .map(|index| match index < arguments.len() {
+                // unused parameters are prefixed to keep the generated code free of warnings
+                true if uses[index] == 0 => format!("_{}", arguments[index].label),
+                true => arguments[index].label.clone(),
+                false => format!("t{}", index - arguments.len()),
+            })
+            .collect::<Vec<_>>();
+
+        let mut source = String::new();
+        let parameters = lowered.instructions[..arguments.len()]
+            .iter()
+            .zip(&names)
+            .map(|(argument, name)| {
+                format!("{}: {}", name, codegen.rust_type(&argument.value_type))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let result_type = codegen.rust_type(&lowered.instructions[lowered.result].value_type);
+        if arguments
+            .iter()
+            .any(|argument| argument.label.chars().any(char::is_uppercase))
+        {
+            writeln!(source, "#[allow(non_snake_case)]").unwrap();
+        }
+        writeln!(
+            source,
+            "pub fn {}({}) -> {} {{",
+            name, parameters, result_type
+        )
+        .unwrap();
+        for (index, instruction) in lowered.instructions.iter().enumerate() {
+            let value = match &instruction.op {
+                Op::Argument(_) => continue,
+                Op::Constant(constant) => constant.clone(),
+                Op::Mul(lhs, rhs) => codegen.mul(
+                    &names[*lhs],
+                    &lowered.instructions[*lhs].value_type,
+                    &names[*rhs],
+                    &lowered.instructions[*rhs].value_type,
+                ),
+                Op::Add(lhs, rhs) => codegen.add(
+                    &names[*lhs],
+                    &lowered.instructions[*lhs].value_type,
+                    &names[*rhs],
+                    &lowered.instructions[*rhs].value_type,
+                ),
+            };
+            writeln!(
+                source,
+                "    let {}: {} = {};",
+                names[index],
+                codegen.rust_type(&instruction.value_type),
+                value
+            )
+            .unwrap();
+        }
+        writeln!(source, "    {}", names[lowered.result]).unwrap();
+        writeln!(source, "}}").unwrap();
+        Ok(source)
+    }
+
+    /// C function `name` that evaluates the polynomial with the Horner scheme,
+    /// `arguments` become its parameters (named by labels, so those have to be valid identifiers)
+    /// and the Horner scheme is nested in their order.
+    ///
+    /// Instructions are typed and shared the same way as for `to_rust_fn`, only their order follows the Horner scheme.
+    /// Supported only for polynomials whose values and `arguments` all have the same type
+    /// that is closed under commutative multiplication.
+    pub fn to_c<Codegen>(
+        &self,
+        name: &str,
+        arguments: &[Symbol<Types>],
+        codegen: &Codegen,
+    ) -> Result<CSource, CodegenError>
+    where
+        Codegen: CValueCodegen<Values>,
+    {
+        let lowered = lower_horner(self, arguments, |value| codegen.constant(value))?;
+        let c_type = codegen.c_type();
+        let names = (0..lowered.instructions.len())
+            .map(|index| match index < arguments.len() {
+                true => arguments[index].label.clone(),
+                false => format!("t{}", index - arguments.len()),
+            })
+            .collect::<Vec<_>>();
+        let parameters = match arguments.is_empty() {
+            true => "void".to_owned(),
+            false => names[..arguments.len()]
+                .iter()
+                .map(|name| format!("{} {}", c_type, name))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        let mut header = String::new();
+        let guard = format!("{}_H", name.to_uppercase());
+        writeln!(header, "#ifndef {}\n#define {}\n", guard, guard).unwrap();
+        writeln!(header, "/* Arguments of {}:", name).unwrap();
+        for (index, argument) in arguments.iter().enumerate() {
+            writeln!(header, " *   {}: {}", index, argument.label).unwrap();
+        }
+        writeln!(header, " */").unwrap();
+        writeln!(header, "{} {}({});\n", c_type, name, parameters).unwrap();
+        writeln!(header, "#endif").unwrap();
+
+        let mut source = String::new();
+        writeln!(source, "{} {}({})\n{{", c_type, name, parameters).unwrap();
+        let uses = lowered.uses();
+        for (name, _) in names
+            .iter()
+            .zip(&uses[..arguments.len()])
+            .filter(|(_, uses)| **uses == 0)
+        {
+            writeln!(source, "    (void){};", name).unwrap();
+        }
+        for (index, instruction) in lowered.instructions.iter().enumerate() {
+            let value = match &instruction.op {
+                Op::Argument(_) => continue,
+                Op::Constant(constant) => constant.clone(),
+                Op::Mul(lhs, rhs) => format!("{} * {}", names[*lhs], names[*rhs]),
+                Op::Add(lhs, rhs) => format!("{} + {}", names[*lhs], names[*rhs]),
+            };
+            writeln!(source, "    const {} {} = {};", c_type, names[index], value).unwrap();
+        }
+        writeln!(source, "    return {};\n}}", names[lowered.result]).unwrap();
+        Ok(CSource { header, source })
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{
+    error::FinalizeError,
+    factor::{Factor, Finalizable, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::Symbol,
+    term::{Term, TermBuilder},
+    traits::{PolyTypes, PolyValues},
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Formal derivative by `symbol`, that is `directional_derivative` in the direction of
+    /// the unit of the symbol type. The unit is omitted from terms whose coefficient has the same type.
+    /// Requires `symbol` to be typed and its type to have a unit.
+    pub fn derivative(&self, symbol: &Symbol<Types>) -> Result<Self, FinalizeError> {
+        let symbol_type = symbol
+            .associated_type
+            .clone()
+            .ok_or(FinalizeError::NoTypeToFinalize)?;
+        let unit = Values::one_with_type(symbol_type).ok_or(FinalizeError::NoValueToFinalize)?;
+        Ok(Derivation {
+            symbol,
+            differential: Differential::Unit(unit),
+        }
+        .polynomial(self))
+    }
+
+    /// Formal derivative by `symbol` in the direction `direction`: every occurrence of `symbol`
+    /// is replaced by `direction` in turn, following the product rule `d(A·x·B) = A·dx·B`.
+    ///
+    /// Factor order is preserved, so `x^n` becomes `Σ x^k·dx·x^(n-1-k)`.
+    /// Only when `x` commutes with itself and with `dx` (per `MulTraits`)
+    /// it is written as `n·x^(n-1)·dx`, with `n` computed by adding the coefficient to itself.
+    /// Terms that do not depend on `symbol` are dropped, if nothing remains the result is
+    /// `Polynomial::zero` of the polynomial type, or a polynomial without terms if there is none.
+    pub fn directional_derivative(
+        &self,
+        symbol: &Symbol<Types>,
+        direction: &Symbol<Types>,
+    ) -> Self {
+        Derivation {
+            symbol,
+            differential: Differential::Variable(direction.clone()),
+        }
+        .polynomial(self)
+    }
+}
+
+enum Differential<Values, Types> {
+    Variable(Symbol<Types>),
+    Unit(Values),
+}
+
+// Derivative of a single factor.
+enum Derived<Values, Types> {
+    Zero,
+    Differential,
+    Polynomial(Polynomial<Values, Types>),
+}
+
+struct Derivation<'a, Values, Types> {
+    symbol: &'a Symbol<Types>,
+    differential: Differential<Values, Types>,
+}
+
+impl<Values, Types> Derivation<'_, Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn polynomial(&self, polynomial: &Polynomial<Values, Types>) -> Polynomial<Values, Types> {
+        match self.sub_poly(polynomial.sub_poly()) {
+            Some(derivative) => derivative,
+            None => polynomial
+                .as_type()
+                .ok()
+                .and_then(|polynomial_type| Polynomial::zero(polynomial_type).ok())
+                .unwrap_or_else(|| {
+                    PolynomialBuilder::new()
+                        .build()
+                        .expect("empty polynomial is always built")
+                }),
+        }
+    }
+
+    // `None` if the derivative is zero
+    fn sub_poly(&self, poly: &SubPoly<Values, Types>) -> Option<Polynomial<Values, Types>> {
+        let mut builder = PolynomialBuilder::new();
+        let mut is_zero = true;
+        for term in poly.terms() {
+            for (index, factor) in term.factors().iter().enumerate() {
+                let derived = self.factor(factor.factor());
+                if factor.power() == 0 || matches!(derived, Derived::Zero) {
+                    continue;
+                }
+                is_zero = false;
+                builder = self.term(builder, term, index, &derived);
+            }
+        }
+        match is_zero {
+            true => None,
+            false => Some(
+                builder
+                    .build()
+                    .expect("terms of an existing polynomial are always built"),
+            ),
+        }
+    }
+
+    fn factor(&self, factor: &Factor<Values, Types>) -> Derived<Values, Types> {
+        match factor {
+            Factor::Value(_) => Derived::Zero,
+            Factor::Variable(variable) if variable.symbol() == self.symbol => Derived::Differential,
+            Factor::Variable(_) => Derived::Zero,
+            Factor::SubPoly(poly) => match self.sub_poly(poly) {
+                Some(derivative) => Derived::Polynomial(derivative),
+                None => Derived::Zero,
+            },
+        }
+    }
+
+    // Terms of the derivative of `term` where only the factor at `index` is differentiated.
+    fn term(
+        &self,
+        mut builder: PolynomialBuilder<Values, Types>,
+        term: &Term<Values, Types>,
+        index: usize,
+        derived: &Derived<Values, Types>,
+    ) -> PolynomialBuilder<Values, Types> {
+        let factors = term.factors();
+        let (base, power) = (factors[index].factor(), factors[index].power());
+        let terms = |builder: PolynomialBuilder<Values, Types>,
+                     coefficient: Values,
+                     middle: &dyn Fn(TermBuilder<Values, Types>) -> TermBuilder<Values, Types>| {
+            let mut term_builder = builder.term_builder(coefficient);
+            for factor in &factors[..index] {
+                term_builder = push(term_builder, factor.factor(), factor.power());
+            }
+            term_builder = middle(term_builder);
+            for factor in &factors[index + 1..] {
+                term_builder = push(term_builder, factor.factor(), factor.power());
+            }
+            term_builder.build()
+        };
+
+        if self.commutes(base, derived) {
+            if let Some(coefficient) = multiple(term.coefficient(), power) {
+                return terms(builder, coefficient, &|term_builder| {
+                    let term_builder = push(term_builder, base, power - 1);
+                    self.push_derived(term_builder, derived, term)
+                });
+            }
+        }
+        for k in 0..power {
+            builder = terms(builder, term.coefficient().clone(), &|term_builder| {
+                let term_builder = push(term_builder, base, k);
+                let term_builder = self.push_derived(term_builder, derived, term);
+                push(term_builder, base, power - 1 - k)
+            });
+        }
+        builder
+    }
+
+    fn push_derived(
+        &self,
+        term_builder: TermBuilder<Values, Types>,
+        derived: &Derived<Values, Types>,
+        term: &Term<Values, Types>,
+    ) -> TermBuilder<Values, Types> {
+        match (derived, &self.differential) {
+            (Derived::Zero, _) => unreachable!("zero derivatives are skipped"),
+            (Derived::Differential, Differential::Variable(direction)) => {
+                term_builder.variable(direction.clone(), 1)
+            }
+            (Derived::Differential, Differential::Unit(unit))
+                if unit.as_type() == term.coefficient().as_type() =>
+            {
+                term_builder
+            }
+            (Derived::Differential, Differential::Unit(unit)) => {
+                term_builder.value(unit.clone(), 1)
+            }
+            (Derived::Polynomial(polynomial), _) => term_builder.polynomial(polynomial.clone(), 1),
+        }
+    }
+
+    // `x` commutes with itself and with its derivative
+    fn commutes(&self, base: &Factor<Values, Types>, derived: &Derived<Values, Types>) -> bool {
+        let derived_type = match (derived, &self.differential) {
+            (Derived::Zero, _) => return true,
+            (Derived::Differential, Differential::Variable(direction)) => {
+                direction.associated_type.clone()
+            }
+            (Derived::Differential, Differential::Unit(unit)) => Some(unit.as_type()),
+            (Derived::Polynomial(polynomial), _) => polynomial.as_type().ok(),
+        };
+        match (base.finalize_type(), derived_type) {
+            (Ok(base_type), Some(derived_type)) => {
+                (base_type.clone() * base_type.clone()).commutative
+                    && (base_type * derived_type).commutative
+            }
+            _ => false,
+        }
+    }
+}
+
+// Factors with zero power are omitted.
+pub(crate) fn push<Values, Types>(
+    term_builder: TermBuilder<Values, Types>,
+    factor: &Factor<Values, Types>,
+    power: u64,
+) -> TermBuilder<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    if power == 0 {
+        return term_builder;
+    }
+    match factor {
+        Factor::Value(value) => term_builder.value(value.value().clone(), power),
+        Factor::Variable(variable) => term_builder.variable(variable.symbol().clone(), power),
+        Factor::SubPoly(poly) => term_builder.polynomial(poly.clone().into(), power),
+    }
+}
+
+// `value` added to itself `times` times, `times` is positive.
+fn multiple<Values, Types>(value: &Values, times: u64) -> Option<Values>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut result: Option<Values> = None;
+    let mut doubled = value.clone();
+    let mut times = times;
+    loop {
+        if times & 1 == 1 {
+            result = Some(match result {
+                Some(result) => (result + doubled.clone())?,
+                None => doubled.clone(),
+            });
+        }
+        times >>= 1;
+        if times == 0 {
+            return result;
+        }
+        doubled = (doubled.clone() + doubled)?;
+    }
+}
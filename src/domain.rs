@@ -0,0 +1,124 @@
+use crate::{
+    error::MapError,
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::{Symbol, SymbolInfo, SymbolsProvider},
+    traits::{PolyTypes, PolyValues},
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Rebuilds the polynomial over another domain, for example reducing integers modulo some prime.
+    ///
+    /// Every value is mapped with `value_map` and every type with `type_map`,
+    /// symbols are linked by label to the ones of `symbols` (missing ones are added with mapped types).
+    /// The maps are expected to be a homomorphism, so it is checked that they agree with each other:
+    /// mapped values and symbols must have mapped types, and if the polynomial can be typed,
+    /// the mapped one must have the mapped type as well.
+    pub fn map_domain<TargetValues, TargetTypes, ValueMap, TypeMap>(
+        &self,
+        value_map: ValueMap,
+        type_map: TypeMap,
+        symbols: &SymbolsProvider<TargetTypes>,
+    ) -> Result<Polynomial<TargetValues, TargetTypes>, MapError>
+    where
+        TargetTypes: PolyTypes<TargetTypes>,
+        TargetValues: PolyValues<TargetTypes, TargetValues>,
+        ValueMap: Fn(&Values) -> TargetValues,
+        TypeMap: Fn(&Types) -> TargetTypes,
+    {
+        let mapper = DomainMapper {
+            value_map,
+            type_map,
+            symbols,
+        };
+        let mapped = mapper.sub_poly(self.sub_poly())?;
+        if let Ok(original_type) = self.as_type() {
+            if mapped.as_type().ok() != Some((mapper.type_map)(&original_type)) {
+                return Err(MapError::TypeMismatch);
+            }
+        }
+        Ok(mapped)
+    }
+}
+
+struct DomainMapper<'a, ValueMap, TypeMap, TargetTypes> {
+    value_map: ValueMap,
+    type_map: TypeMap,
+    symbols: &'a SymbolsProvider<TargetTypes>,
+}
+
+impl<ValueMap, TypeMap, TargetTypes> DomainMapper<'_, ValueMap, TypeMap, TargetTypes>
+where
+    TargetTypes: PolyTypes<TargetTypes>,
+{
+    fn value<Values, Types, TargetValues>(&self, value: &Values) -> Result<TargetValues, MapError>
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+        TargetValues: PolyValues<TargetTypes, TargetValues>,
+        ValueMap: Fn(&Values) -> TargetValues,
+        TypeMap: Fn(&Types) -> TargetTypes,
+    {
+        let mapped = (self.value_map)(value);
+        match mapped.as_type() == (self.type_map)(&value.as_type()) {
+            true => Ok(mapped),
+            false => Err(MapError::ValueTypeMismatch),
+        }
+    }
+
+    fn symbol<Types>(&self, symbol: &Symbol<Types>) -> Result<Symbol<TargetTypes>, MapError>
+    where
+        TypeMap: Fn(&Types) -> TargetTypes,
+    {
+        let associated_type = symbol.associated_type.as_ref().map(&self.type_map);
+        match self.symbols.get(&symbol.label) {
+            Some(mapped) if mapped.associated_type != associated_type => {
+                Err(MapError::SymbolTypeMismatch(symbol.label.clone()))
+            }
+            Some(mapped) => Ok(mapped),
+            None => Ok(self.symbols.add(SymbolInfo {
+                label: symbol.label.clone(),
+                associated_type,
+                latex: symbol.latex.clone(),
+            })),
+        }
+    }
+
+    fn sub_poly<Values, Types, TargetValues>(
+        &self,
+        poly: &SubPoly<Values, Types>,
+    ) -> Result<Polynomial<TargetValues, TargetTypes>, MapError>
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+        TargetValues: PolyValues<TargetTypes, TargetValues>,
+        ValueMap: Fn(&Values) -> TargetValues,
+        TypeMap: Fn(&Types) -> TargetTypes,
+    {
+        let mut builder = PolynomialBuilder::new();
+        for term in poly.terms() {
+            let mut term_builder = builder.term_builder(self.value(term.coefficient())?);
+            for factor in term.factors() {
+                term_builder = match factor.factor() {
+                    Factor::Value(value) => {
+                        term_builder.value(self.value(value.value())?, factor.power())
+                    }
+                    Factor::Variable(variable) => {
+                        term_builder.variable(self.symbol(variable.symbol())?, factor.power())
+                    }
+                    Factor::SubPoly(poly) => {
+                        term_builder.polynomial(self.sub_poly(poly)?, factor.power())
+                    }
+                };
+            }
+            builder = term_builder.build();
+        }
+        Ok(builder
+            .build()
+            .expect("terms of an existing polynomial are always built"))
+    }
+}
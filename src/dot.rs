@@ -0,0 +1,135 @@
+use std::fmt::{Display, Write};
+
+use crate::{
+    error::FinalizeError,
+    factor::{Factor, Finalizable, SubPoly},
+    term::{MonomialFactor, Term},
+    traits::{PolyTypes, PolyValues},
+};
+
+// Writes nodes in the same order as Debug does: sub-polynomial, its terms, their factors and so on.
+// Every node gets sequential id, edges always go from parent to child.
+pub struct DotWriter {
+    output: String,
+    nodes: usize,
+}
+
+impl DotWriter {
+    pub fn new() -> Self {
+        DotWriter {
+            output: String::from("digraph polynomial {\n    node [shape=box];\n"),
+            nodes: 0,
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        self.output.push_str("}\n");
+        self.output
+    }
+
+    fn node(&mut self, parent: Option<usize>, lines: &[String]) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+        let label = lines
+            .iter()
+            .map(|line| escape(line))
+            .collect::<Vec<_>>()
+            .join("\\n");
+        writeln!(self.output, "    n{} [label=\"{}\"];", id, label).unwrap();
+        if let Some(parent) = parent {
+            writeln!(self.output, "    n{} -> n{};", parent, id).unwrap();
+        }
+        id
+    }
+
+    pub fn sub_poly<Values, Types>(
+        &mut self,
+        parent: Option<usize>,
+        sub_poly: &SubPoly<Values, Types>,
+    ) where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let id = self.node(
+            parent,
+            &["SubPoly".to_owned(), type_line(sub_poly.finalize_type())],
+        );
+        sub_poly.terms().iter().for_each(|term| self.term(id, term));
+    }
+
+    fn term<Values, Types>(&mut self, parent: usize, term: &Term<Values, Types>)
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let id = self.node(
+            Some(parent),
+            &[
+                "Term".to_owned(),
+                format!("coefficient: {}", term.coefficient()),
+                type_line(term.finalize_type()),
+            ],
+        );
+        term.factors()
+            .iter()
+            .for_each(|monomial_factor| self.monomial_factor(id, monomial_factor));
+    }
+
+    fn monomial_factor<Values, Types>(
+        &mut self,
+        parent: usize,
+        monomial_factor: &MonomialFactor<Values, Types>,
+    ) where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let id = self.node(
+            Some(parent),
+            &[
+                "MonomialFactor".to_owned(),
+                format!("power: {}", monomial_factor.power()),
+                type_line(monomial_factor.finalize_type()),
+            ],
+        );
+        match monomial_factor.factor() {
+            Factor::Value(value) => {
+                self.node(
+                    Some(id),
+                    &[
+                        "Value".to_owned(),
+                        value.value().to_string(),
+                        type_line(Ok(value.value().as_type())),
+                    ],
+                );
+            }
+            Factor::Variable(variable) => {
+                let symbol = variable.symbol();
+                self.node(
+                    Some(id),
+                    &[
+                        "Variable".to_owned(),
+                        format!("symbol: {}", symbol),
+                        type_line(
+                            symbol
+                                .associated_type
+                                .clone()
+                                .ok_or(FinalizeError::NoTypeToFinalize),
+                        ),
+                    ],
+                );
+            }
+            Factor::SubPoly(sub_poly) => self.sub_poly(Some(id), sub_poly),
+        }
+    }
+}
+
+fn type_line<Types: Display>(finalized_type: Result<Types, FinalizeError>) -> String {
+    match finalized_type {
+        Ok(finalized_type) => format!("type: {}", finalized_type),
+        Err(err) => format!("type error: {:?}", err),
+    }
+}
+
+fn escape(line: &str) -> String {
+    line.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,341 @@
+use std::{
+    hash::{Hash, Hasher},
+    mem,
+};
+
+use crate::{
+    factor::{Factor, SubPoly, Value, Variable},
+    polynomial::Polynomial,
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    traits::{PolyTypes, PolyValues},
+};
+
+// Structural equality: the same tree with equal values, the same symbols and the same powers.
+// Cached types are not compared.
+
+impl<Values: PartialEq, Types> PartialEq for Polynomial<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sub_poly() == other.sub_poly()
+    }
+}
+
+impl<Values: Eq, Types> Eq for Polynomial<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for Polynomial<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sub_poly().hash(state);
+    }
+}
+
+impl<Values: PartialEq, Types> PartialEq for SubPoly<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.terms() == other.terms()
+    }
+}
+
+impl<Values: Eq, Types> Eq for SubPoly<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for SubPoly<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.terms().hash(state);
+    }
+}
+
+impl<Values: PartialEq, Types> PartialEq for Term<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficient() == other.coefficient() && self.factors() == other.factors()
+    }
+}
+
+impl<Values: Eq, Types> Eq for Term<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for Term<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.coefficient().hash(state);
+        self.factors().hash(state);
+    }
+}
+
+impl<Values: PartialEq, Types> PartialEq for MonomialFactor<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.power() == other.power() && self.factor() == other.factor()
+    }
+}
+
+impl<Values: Eq, Types> Eq for MonomialFactor<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for MonomialFactor<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.factor().hash(state);
+        self.power().hash(state);
+    }
+}
+
+impl<Values: PartialEq, Types> PartialEq for Factor<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Factor::Value(lhs), Factor::Value(rhs)) => lhs == rhs,
+            (Factor::Variable(lhs), Factor::Variable(rhs)) => lhs == rhs,
+            (Factor::SubPoly(lhs), Factor::SubPoly(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl<Values: Eq, Types> Eq for Factor<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for Factor<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Factor::Value(value) => value.hash(state),
+            Factor::Variable(variable) => variable.hash(state),
+            Factor::SubPoly(poly) => poly.hash(state),
+        }
+    }
+}
+
+impl<Values: PartialEq, Types> PartialEq for Value<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl<Values: Eq, Types> Eq for Value<Values, Types> {}
+
+impl<Values: Hash, Types> Hash for Value<Values, Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state);
+    }
+}
+
+impl<Types> PartialEq for Variable<Types> {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol() == other.symbol()
+    }
+}
+
+impl<Types> Eq for Variable<Types> {}
+
+impl<Types> Hash for Variable<Types> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.symbol().hash(state);
+    }
+}
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + PartialEq,
+{
+    /// Whether both polynomials have the same tree: terms and factors in the same order
+    /// with equal values, the same symbols and the same powers (the same as `==`).
+    pub fn structurally_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Whether the polynomials are equal as expressions of their symbols.
+    ///
+    /// Both are expanded into sums of words, where powers and sub-polynomials are multiplied out
+    /// and values are merged into coefficients where they commute with the preceding factors.
+    /// Factors of a word are reordered only where they commute (per `MulTraits`),
+    /// untyped symbols are assumed to commute with nothing.
+    /// Coefficients of equal words are added up and zero ones (equal to `zero_with_type`) are dropped.
+    /// If some coefficients of equal words can not be added up (`+` returns `None`),
+    /// the polynomial is equivalent only to polynomials that are structurally equal to it.
+    pub fn equivalent(&self, other: &Self) -> bool {
+        if self == other {
+            return true;
+        }
+        let (Some(lhs), Some(rhs)) = (canonical(self.sub_poly()), canonical(other.sub_poly()))
+        else {
+            return false;
+        };
+        // every word of `rhs` is matched at most once, so this compares multisets
+        let mut unmatched = rhs;
+        lhs.iter().all(|(word, coefficient)| {
+            match unmatched
+                .iter()
+                .position(|(other_word, other_coefficient)| {
+                    word == other_word && coefficient == other_coefficient
+                }) {
+                Some(index) => {
+                    unmatched.swap_remove(index);
+                    true
+                }
+                None => false,
+            }
+        }) && unmatched.is_empty()
+    }
+}
+
+// Factor of a word, powers are expanded.
+#[derive(Clone)]
+enum Letter<Values, Types> {
+    Value(Values),
+    Symbol(Symbol<Types>),
+}
+
+impl<Values: PartialEq, Types> PartialEq for Letter<Values, Types> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Letter::Value(lhs), Letter::Value(rhs)) => lhs == rhs,
+            (Letter::Symbol(lhs), Letter::Symbol(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+type Word<Values, Types> = Vec<Letter<Values, Types>>;
+
+// Sum of words with their coefficients.
+type Expansion<Values, Types> = Vec<(Word<Values, Types>, Values)>;
+
+fn letter_type<Values, Types>(letter: &Letter<Values, Types>) -> Option<Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    match letter {
+        Letter::Value(value) => Some(value.as_type()),
+        Letter::Symbol(symbol) => symbol.associated_type.clone(),
+    }
+}
+
+fn commute<Values, Types>(lhs: &Letter<Values, Types>, rhs: &Letter<Values, Types>) -> bool
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    match (letter_type(lhs), letter_type(rhs)) {
+        (Some(lhs), Some(rhs)) => (lhs * rhs).commutative,
+        _ => false,
+    }
+}
+
+// Expansion where every word appears once, `None` if coefficients of some equal words can not be added up.
+fn canonical<Values, Types>(poly: &SubPoly<Values, Types>) -> Option<Expansion<Values, Types>>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + PartialEq,
+{
+    let mut sum: Expansion<Values, Types> = vec![];
+    for (word, coefficient) in expand(poly) {
+        let (word, coefficient) = normalize(word, coefficient);
+        match sum.iter_mut().find(|(known, _)| *known == word) {
+            Some((_, known)) => *known = (known.clone() + coefficient)?,
+            None => sum.push((word, coefficient)),
+        }
+    }
+    sum.retain(|(_, coefficient)| {
+        Values::zero_with_type(coefficient.as_type()).as_ref() != Some(coefficient)
+    });
+    Some(sum)
+}
+
+fn expand<Values, Types>(poly: &SubPoly<Values, Types>) -> Expansion<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut sum = vec![];
+    for term in poly.terms() {
+        let mut product = vec![(vec![], term.coefficient().clone())];
+        for factor in term.factors() {
+            let base = match factor.factor() {
+                Factor::Value(value) => vec![(vec![Letter::Value(value.value().clone())], None)],
+                Factor::Variable(variable) => {
+                    vec![(vec![Letter::Symbol(variable.symbol().clone())], None)]
+                }
+                Factor::SubPoly(poly) => expand(poly)
+                    .into_iter()
+                    .map(|(word, coefficient)| (word, Some(coefficient)))
+                    .collect(),
+            };
+            for _ in 0..factor.power() {
+                product = multiply(product, &base);
+            }
+        }
+        sum.extend(product);
+    }
+    sum
+}
+
+// Coefficients of `rhs` (`None` for units) are moved to the front when they commute with the word of `lhs`.
+fn multiply<Values, Types>(
+    lhs: Expansion<Values, Types>,
+    rhs: &[(Word<Values, Types>, Option<Values>)],
+) -> Expansion<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut product = vec![];
+    for (lhs_word, lhs_coefficient) in &lhs {
+        for (rhs_word, rhs_coefficient) in rhs {
+            let mut word = lhs_word.clone();
+            let coefficient = match rhs_coefficient {
+                None => lhs_coefficient.clone(),
+                Some(rhs_coefficient) => {
+                    let letter = Letter::Value(rhs_coefficient.clone());
+                    let merged = match word.iter().all(|known| commute(known, &letter)) {
+                        true => lhs_coefficient.clone() * rhs_coefficient.clone(),
+                        false => None,
+                    };
+                    merged.unwrap_or_else(|| {
+                        word.push(letter);
+                        lhs_coefficient.clone()
+                    })
+                }
+            };
+            word.extend(rhs_word.iter().cloned());
+            product.push((word, coefficient));
+        }
+    }
+    product
+}
+
+// Values that commute with everything before them are merged into the coefficient,
+// then the word is brought to its lexicographically smallest form among the ones
+// reachable by swapping commuting letters (symbols by id first, then values in the order they appear).
+fn normalize<Values, Types>(
+    word: Word<Values, Types>,
+    mut coefficient: Values,
+) -> (Word<Values, Types>, Values)
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut letters: Word<Values, Types> = vec![];
+    for letter in word {
+        if let Letter::Value(value) = &letter {
+            if letters.iter().all(|known| commute(known, &letter)) {
+                if let Some(merged) = coefficient.clone() * value.clone() {
+                    coefficient = merged;
+                    continue;
+                }
+            }
+        }
+        letters.push(letter);
+    }
+
+    let key = |letter: &Letter<Values, Types>| match letter {
+        Letter::Symbol(symbol) => Some(symbol.id()),
+        Letter::Value(_) => None,
+    };
+    let mut normalized = vec![];
+    while !letters.is_empty() {
+        // letters that can be moved to the front, the first one is taken among equal keys
+        let index = (0..letters.len())
+            .filter(|index| {
+                letters[..*index]
+                    .iter()
+                    .all(|before| commute(before, &letters[*index]))
+            })
+            .min_by_key(|index| (key(&letters[*index]).is_none(), key(&letters[*index])))
+            .expect("the first letter can always be moved");
+        normalized.push(letters.remove(index));
+    }
+    (normalized, coefficient)
+}
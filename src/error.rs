@@ -1,3 +1,4 @@
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 pub enum BuilderError {
     CoefficientError,
@@ -14,4 +15,131 @@ pub enum SubstitutionError {
 pub enum FinalizeError {
     NoValueToFinalize,
     NoTypeToFinalize,
+    MismatchedTypes,
+    /// Binding of `Polynomial::evaluate_batch` does not provide exactly one value per symbol.
+    MismatchedBinding,
+}
+
+/// Errors of `PolyParser`, positions are byte offsets in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    UnexpectedInput(usize),
+    InvalidPower(usize),
+    MissingCoefficient(usize),
+}
+
+/// Errors of code generation (see `Polynomial::to_rust_fn` and `Polynomial::to_c`).
+#[derive(Debug, Clone)]
+pub enum CodegenError {
+    /// Symbol of the polynomial is not among the arguments of the generated function.
+    UnboundSymbol(String),
+    /// Argument has no associated type, so its type in the generated code is unknown.
+    UntypedSymbol(String),
+    TypeError(FinalizeError),
+    /// Backend supports only a single commutative type, but the polynomial uses the given one as well.
+    UnsupportedType(String),
+}
+
+/// Errors of `Polynomial::from_json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// Input is not a JSON document, contains the position of the error (both start at 1).
+    Syntax {
+        line: usize,
+        column: usize,
+    },
+    UnsupportedVersion(u64),
+    /// Document does not follow the schema, contains the name of the missing or malformed field.
+    InvalidDocument(String),
+    /// Node kind that is reserved by the schema but can not be represented (for example `apply`).
+    UnsupportedNode(String),
+    UnknownSymbol(String),
+    /// Value decoding callback has failed.
+    InvalidValue,
+}
+
+/// Errors of `Polynomial::map_domain`, reported when typing does not commute with the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    /// Mapped value has a different type than the mapped type of the original value.
+    ValueTypeMismatch,
+    /// Symbol with the same label is already declared in the target provider with a different type.
+    SymbolTypeMismatch(String),
+    /// Mapped polynomial has a different type than the mapped type of the original one.
+    TypeMismatch,
+}
+
+/// Errors of `PolynomialMap::new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolynomialMapError {
+    /// Component uses a symbol that is not in the provider of the map, contains its label.
+    ForeignSymbol(String),
+}
+
+/// Errors of `PolynomialMap::compose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompositionError {
+    /// Number of substituted symbols differs from the number of components of the inner map,
+    /// contains both of them.
+    ArityMismatch(usize, usize),
+    /// Symbol would be substituted by a polynomial of another type, contains its label.
+    TypeMismatch(String),
+    /// Component of the inner map uses a symbol that is not in the provider of the outer one, contains its label.
+    ForeignSymbol(String),
+}
+
+/// Errors of `Polynomial::integrate`.
+#[derive(Debug, Clone)]
+pub enum IntegrationError {
+    /// Symbol has no associated type, contains its label.
+    UntypedSymbol(String),
+    /// Type of the symbol is not closed under commutative multiplication
+    /// or does not commute with the given type used in the polynomial.
+    NonCommutative(String),
+    TypeError(FinalizeError),
+    /// Coefficient is not divisible by the given number.
+    Indivisible(u64),
+    /// Power of the symbol in some term does not fit into `u64`.
+    PowerOverflow,
+}
+
+/// Errors of univariate algorithms (see `Polynomial::div_rem`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnivariateError {
+    /// Symbol has no associated type, contains its label.
+    UntypedSymbol(String),
+    /// Polynomial contains another symbol, contains its label.
+    NotUnivariate(String),
+    /// Type of the symbol is not closed under commutative multiplication or has no zero and one,
+    /// or a value of the polynomial has another type; contains the type.
+    UnsupportedType(String),
+    DivisionByZero,
+}
+
+/// Errors of Gröbner basis computations (see `PolynomialMap::groebner_basis`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroebnerError {
+    /// No symbols are given, so the coefficient type is unknown.
+    NoVariables,
+    /// Symbol has no associated type, contains its label.
+    UntypedSymbol(String),
+    /// Polynomial contains a symbol that is not among the given ones, contains its label.
+    UnknownSymbol(String),
+    /// Type of the symbols is not closed under commutative multiplication, contains it.
+    NonCommutative(String),
+    /// Symbols have different types, the type has no zero and one, or a value has another type;
+    /// contains the type.
+    UnsupportedType(String),
+}
+
+/// Errors of `Polynomial::probably_equal`.
+#[derive(Debug, Clone)]
+pub enum IdentityTestError {
+    /// Symbol has no associated type, contains its label.
+    UntypedSymbol(String),
+    /// Sampler can not produce values of the type, contains it.
+    UnsupportedType(String),
+    /// One of the polynomials could not be evaluated at the sampled values.
+    TypeError(FinalizeError),
 }
@@ -6,31 +6,49 @@ use std::{
 use crate::{
     error::FinalizeError,
     symbol::Symbol,
+    sync::{try_fold_ordered, OnceCell},
     term::Term,
     traits::{PolyTypes, PolyValues},
 };
 
-pub trait Factorable<Values, Types>
+pub(crate) trait Factorable<Values, Types>
 where
     Self: Substitutiable<Values, Types, Output = Factor<Values, Types>>,
     Self: Finalizable<Values, Types>,
     Types: PolyTypes<Types>,
     Values: PolyValues<Types, Values>,
 {
-    fn as_factor(self) -> Factor<Values, Types>;
+    fn into_factor(self) -> Factor<Values, Types>;
 }
 
-pub trait Substitutiable<Values, Types> {
+pub(crate) trait Substitutiable<Values, Types> {
     type Output;
 
     fn substitute(self, to: Symbol<Types>, factor: Factor<Values, Types>) -> Self::Output;
 }
 
-pub trait Finalizable<Values, Types> {
+pub(crate) trait Finalizable<Values, Types> {
     fn finalize_type(&self) -> Result<Types, FinalizeError>;
     fn finalize_value(self) -> Result<Values, FinalizeError>;
 }
 
+pub(crate) trait ToLatex {
+    fn to_latex(&self) -> String;
+}
+
+// Result of finalize_type memoized inside a node. Nodes are never changed in place
+// (substitution and folding build new ones), so the cache never has to be invalidated explicitly.
+pub(crate) type TypeCache<Types> = OnceCell<Result<Types, FinalizeError>>;
+
+// Evaluates every subtree that has no variables left in it, so that repeated evaluations
+// (for example with different values substituted for the remaining variables) do not redo that work.
+// Subtrees that fail to finalize are left as they are, so the error surfaces on the actual evaluation.
+pub(crate) trait Foldable {
+    type Output;
+
+    fn fold_constants(self) -> Self::Output;
+}
+
 #[derive(Clone)]
 pub enum Factor<Values, Types> {
     Value(Value<Values, Types>),
@@ -43,7 +61,7 @@ where
     Types: PolyTypes<Types>,
     Values: PolyValues<Types, Values>,
 {
-    fn as_factor(self) -> Factor<Values, Types> {
+    fn into_factor(self) -> Factor<Values, Types> {
         self
     }
 }
@@ -88,6 +106,35 @@ where
     }
 }
 
+impl<Values, Types> Foldable for Factor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    type Output = Self;
+
+    fn fold_constants(self) -> Self::Output {
+        match self {
+            Factor::Value(_) | Factor::Variable(_) => self,
+            Factor::SubPoly(sub_poly) => sub_poly.fold_constants(),
+        }
+    }
+}
+
+impl<Values, Types> ToLatex for Factor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn to_latex(&self) -> String {
+        match self {
+            Factor::Value(value) => value.value.to_latex(),
+            Factor::Variable(variable) => variable.symbol.to_latex(),
+            Factor::SubPoly(sub_poly) => format!("\\left( {} \\right)", sub_poly.to_latex()),
+        }
+    }
+}
+
 impl<Values, Types> Debug for Factor<Values, Types>
 where
     Types: PolyTypes<Types>,
@@ -123,12 +170,16 @@ pub struct Value<Values, Types> {
 }
 
 impl<Values, Types> Value<Values, Types> {
-    pub fn new(value: Values) -> Self {
+    pub(crate) fn new(value: Values) -> Self {
         Value {
             value,
             _marker: PhantomData,
         }
     }
+
+    pub fn value(&self) -> &Values {
+        &self.value
+    }
 }
 
 impl<Values, Types> Factorable<Values, Types> for Value<Values, Types>
@@ -136,7 +187,7 @@ where
     Types: PolyTypes<Types>,
     Values: PolyValues<Types, Values>,
 {
-    fn as_factor(self) -> Factor<Values, Types> {
+    fn into_factor(self) -> Factor<Values, Types> {
         Factor::Value(self)
     }
 }
@@ -150,7 +201,7 @@ where
     type Output = Factor<Values, Types>;
 
     fn substitute(self, _: Symbol<Types>, _: Factor<Values, Types>) -> Self::Output {
-        self.as_factor()
+        self.into_factor()
     }
 }
 
@@ -195,9 +246,13 @@ pub struct Variable<Types> {
 }
 
 impl<Types> Variable<Types> {
-    pub fn new(symbol: Symbol<Types>) -> Self {
+    pub(crate) fn new(symbol: Symbol<Types>) -> Self {
         Variable { symbol }
     }
+
+    pub fn symbol(&self) -> &Symbol<Types> {
+        &self.symbol
+    }
 }
 
 impl<Values, Types> Factorable<Values, Types> for Variable<Types>
@@ -205,7 +260,7 @@ where
     Types: PolyTypes<Types>,
     Values: PolyValues<Types, Values>,
 {
-    fn as_factor(self) -> Factor<Values, Types> {
+    fn into_factor(self) -> Factor<Values, Types> {
         Factor::Variable(self)
     }
 }
@@ -222,7 +277,7 @@ where
         if self.symbol == to {
             factor
         } else {
-            self.as_factor()
+            self.into_factor()
         }
     }
 }
@@ -266,11 +321,45 @@ where
 #[derive(Clone)]
 pub struct SubPoly<Values, Types> {
     parts: Vec<Term<Values, Types>>,
+    finalized_type: TypeCache<Types>,
 }
 
 impl<Values, Types> SubPoly<Values, Types> {
-    pub fn new(parts: Vec<Term<Values, Types>>) -> Self {
-        SubPoly { parts }
+    pub(crate) fn new(parts: Vec<Term<Values, Types>>) -> Self {
+        SubPoly {
+            parts,
+            finalized_type: TypeCache::new(),
+        }
+    }
+
+    pub fn terms(&self) -> &[Term<Values, Types>] {
+        &self.parts
+    }
+}
+
+impl<Values, Types> SubPoly<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn infer_type(&self) -> Result<Types, FinalizeError> {
+        let parts_type = self
+            .parts
+            .iter()
+            .try_fold(None, |pref: Option<Types>, term| {
+                let term_type = term.finalize_type()?;
+                match pref {
+                    None => Ok(Some(term_type)),
+                    Some(pref_type) => (pref_type + term_type)
+                        .map(|result_type| Some(result_type))
+                        .ok_or(FinalizeError::NoTypeToFinalize),
+                }
+            });
+        match parts_type {
+            Ok(Some(parts_type)) => Ok(parts_type),
+            Ok(None) => Err(FinalizeError::NoTypeToFinalize),
+            Err(err) => Err(err),
+        }
     }
 }
 
@@ -279,7 +368,7 @@ where
     Types: PolyTypes<Types>,
     Values: PolyValues<Types, Values>,
 {
-    fn as_factor(self) -> Factor<Values, Types> {
+    fn into_factor(self) -> Factor<Values, Types> {
         Factor::SubPoly(self)
     }
 }
@@ -293,14 +382,13 @@ where
     type Output = Factor<Values, Types>;
 
     fn substitute(self, to: Symbol<Types>, factor: Factor<Values, Types>) -> Self::Output {
-        SubPoly {
-            parts: self
-                .parts
+        SubPoly::new(
+            self.parts
                 .into_iter()
                 .map(|term| term.substitute(to.clone(), factor.clone()))
                 .collect(),
-        }
-        .as_factor()
+        )
+        .into_factor()
     }
 }
 
@@ -311,43 +399,68 @@ where
     Values: PolyValues<Types, Values>,
 {
     fn finalize_type(&self) -> Result<Types, FinalizeError> {
-        let parts_type = self
-            .parts
-            .iter()
-            .try_fold(None, |pref: Option<Types>, term| {
-                let term_type = term.finalize_type()?;
-                match pref {
-                    None => Ok(Some(term_type)),
-                    Some(pref_type) => (pref_type + term_type)
-                        .map(|result_type| Some(result_type))
-                        .ok_or(FinalizeError::NoTypeToFinalize),
-                }
-            });
-        match parts_type {
-            Ok(Some(parts_type)) => Ok(parts_type),
-            Ok(None) => Err(FinalizeError::NoTypeToFinalize),
-            Err(err) => Err(err),
-        }
+        self.finalized_type
+            .get_or_init(|| self.infer_type())
+            .clone()
     }
 
     fn finalize_value(self) -> Result<Values, FinalizeError> {
-        let parts_values = self
-            .parts
-            .into_iter()
-            .try_fold(None, |pref: Option<Values>, term| {
-                let term_value = term.finalize_value()?;
-                match pref {
-                    None => Ok(Some(term_value)),
-                    Some(pref_value) => (pref_value + term_value)
-                        .map(|result_values| Some(result_values))
-                        .ok_or(FinalizeError::NoValueToFinalize),
-                }
-            });
-        match parts_values {
-            Ok(Some(parts_values)) => Ok(parts_values),
-            Ok(None) => Err(FinalizeError::NoTypeToFinalize),
-            Err(err) => Err(err),
+        // terms are evaluated independently (possibly concurrently), but summed strictly in order
+        try_fold_ordered(
+            self.parts,
+            |term| term.finalize_value(),
+            |sum, term_value| (sum + term_value).ok_or(FinalizeError::NoValueToFinalize),
+        )?
+        .ok_or(FinalizeError::NoTypeToFinalize)
+    }
+}
+
+impl<Values, Types> Foldable for SubPoly<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    type Output = Factor<Values, Types>;
+
+    fn fold_constants(self) -> Self::Output {
+        let folded = SubPoly::new(
+            self.parts
+                .into_iter()
+                .map(|term| term.fold_constants())
+                .collect(),
+        );
+        if folded.parts.is_empty() || !folded.parts.iter().all(|term| term.is_constant()) {
+            return folded.into_factor();
+        }
+        match folded.clone().finalize_value() {
+            Ok(value) => Factor::Value(Value::new(value)),
+            Err(_) => folded.into_factor(),
+        }
+    }
+}
+
+impl<Values, Types> ToLatex for SubPoly<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn to_latex(&self) -> String {
+        // negative coefficients (see `PolyValues::is_negative`) are written as subtraction of the negated term
+        let mut result = String::new();
+        for (index, term) in self.parts.iter().enumerate() {
+            let negative = term.coefficient().is_negative();
+            match (index, negative) {
+                (0, false) => {}
+                (0, true) => result.push('-'),
+                (_, false) => result.push_str(" + "),
+                (_, true) => result.push_str(" - "),
+            }
+            match negative {
+                true => result.push_str(&(-term.clone()).to_latex()),
+                false => result.push_str(&term.to_latex()),
+            }
         }
+        result
     }
 }
 
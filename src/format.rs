@@ -0,0 +1,429 @@
+use crate::{
+    error::ParseError,
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::{Symbol, SymbolsProvider},
+    term::{MonomialFactor, Term, TermBuilder},
+    traits::{PolyTypes, PolyValues},
+};
+
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Configurable plain text printer of polynomials.
+///
+/// `PolyFormatter::new()` prints exactly the same text as `Display`,
+/// every option can be changed with corresponding builder method.
+/// Output of any configuration can be read back with `PolyParser`.
+#[derive(Clone, Debug)]
+pub struct PolyFormatter {
+    hide_unit_coefficients: bool,
+    negative_as_subtraction: bool,
+    superscript_powers: bool,
+    explicit_multiplication: bool,
+    minimal_parentheses: bool,
+    line_width: Option<usize>,
+}
+
+impl Default for PolyFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PolyFormatter {
+    pub fn new() -> Self {
+        PolyFormatter {
+            hide_unit_coefficients: false,
+            negative_as_subtraction: false,
+            superscript_powers: false,
+            explicit_multiplication: false,
+            minimal_parentheses: false,
+            line_width: None,
+        }
+    }
+
+    /// Omits coefficients for which `PolyValues::is_one` holds (unless term has no factors).
+    pub fn hide_unit_coefficients(mut self, enabled: bool) -> Self {
+        self.hide_unit_coefficients = enabled;
+        self
+    }
+
+    /// Prints `a - b` instead of `a + -b` for coefficients for which `PolyValues::is_negative` holds.
+    pub fn negative_as_subtraction(mut self, enabled: bool) -> Self {
+        self.negative_as_subtraction = enabled;
+        self
+    }
+
+    /// Prints `x²` instead of `x^2`.
+    pub fn superscript_powers(mut self, enabled: bool) -> Self {
+        self.superscript_powers = enabled;
+        self
+    }
+
+    /// Prints `a * x` instead of `a x`.
+    pub fn explicit_multiplication(mut self, enabled: bool) -> Self {
+        self.explicit_multiplication = enabled;
+        self
+    }
+
+    /// Omits parentheses around sub-polynomials that consist of one term and are not raised to a power.
+    pub fn minimal_parentheses(mut self, enabled: bool) -> Self {
+        self.minimal_parentheses = enabled;
+        self
+    }
+
+    /// Wraps top level sum, so that lines are no longer than `width` characters (if terms allow it).
+    /// Lines are broken only before `+` or `-` between terms.
+    pub fn line_width(mut self, width: Option<usize>) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    pub fn format<Values, Types>(&self, polynomial: &Polynomial<Values, Types>) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let pieces = self.sum_pieces(polynomial.sub_poly());
+        let Some(width) = self.line_width else {
+            return pieces.join(" ");
+        };
+        let mut result = String::new();
+        let mut line_len = 0;
+        for piece in pieces {
+            let piece_len = piece.chars().count();
+            if line_len > 0 && line_len + 1 + piece_len > width {
+                result.push('\n');
+                line_len = 0;
+            } else if line_len > 0 {
+                result.push(' ');
+                line_len += 1;
+            }
+            result.push_str(&piece);
+            line_len += piece_len;
+        }
+        result
+    }
+
+    // Terms of the sum, all except the first one prefixed with the operator.
+    fn sum_pieces<Values, Types>(&self, sub_poly: &SubPoly<Values, Types>) -> Vec<String>
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        sub_poly
+            .terms()
+            .iter()
+            .enumerate()
+            .map(|(i, term)| {
+                let negative = self.negative_as_subtraction && term.coefficient().is_negative();
+                let term = self.format_term(term, negative);
+                match (i, negative) {
+                    (0, false) => term,
+                    (0, true) => format!("- {}", term),
+                    (_, false) => format!("+ {}", term),
+                    (_, true) => format!("- {}", term),
+                }
+            })
+            .collect()
+    }
+
+    fn format_term<Values, Types>(&self, term: &Term<Values, Types>, negate: bool) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let coefficient = match negate {
+            true => -term.coefficient().clone(),
+            false => term.coefficient().clone(),
+        };
+        let hide_coefficient =
+            self.hide_unit_coefficients && coefficient.is_one() && !term.is_constant();
+        let separator = match self.explicit_multiplication {
+            true => " * ",
+            false => " ",
+        };
+        (!hide_coefficient)
+            .then(|| coefficient.to_string())
+            .into_iter()
+            .chain(
+                term.factors()
+                    .iter()
+                    .map(|monomial_factor| self.format_monomial_factor(monomial_factor)),
+            )
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn format_monomial_factor<Values, Types>(
+        &self,
+        monomial_factor: &MonomialFactor<Values, Types>,
+    ) -> String
+    where
+        Types: PolyTypes<Types>,
+        Values: PolyValues<Types, Values>,
+    {
+        let base = match monomial_factor.factor() {
+            Factor::Value(value) => value.value().to_string(),
+            Factor::Variable(variable) => variable.symbol().to_string(),
+            Factor::SubPoly(sub_poly) => {
+                let inner = self.sum_pieces(sub_poly);
+                let inner_is_negative = inner.first().is_some_and(|term| term.starts_with('-'));
+                match self.minimal_parentheses
+                    && monomial_factor.power() == 1
+                    && inner.len() == 1
+                    && !inner_is_negative
+                {
+                    true => inner.join(" "),
+                    false => format!("( {} )", inner.join(" ")),
+                }
+            }
+        };
+        match monomial_factor.power() {
+            1 => base,
+            power if self.superscript_powers => {
+                let superscript = power
+                    .to_string()
+                    .chars()
+                    .map(|digit| SUPERSCRIPT_DIGITS[digit.to_digit(10).unwrap() as usize])
+                    .collect::<String>();
+                format!("{}{}", base, superscript)
+            }
+            power => format!("{}^{}", base, power),
+        }
+    }
+}
+
+/// Reads polynomials printed by `Display` or `PolyFormatter` back.
+///
+/// Symbols are looked up by label in the given `SymbolsProvider`,
+/// values are read with `parse_value`, that gets the rest of the input
+/// and returns parsed value with the number of bytes it has consumed.
+/// If coefficients were hidden, `unit_type` has to be set, so that unit coefficient of the right type can be restored.
+pub struct PolyParser<'a, Types, ParseValue> {
+    symbols: &'a SymbolsProvider<Types>,
+    parse_value: ParseValue,
+    unit_type: Option<Types>,
+}
+
+enum ParsedItem<Values, Types> {
+    Value(Values),
+    Variable(Symbol<Types>),
+    Polynomial(Polynomial<Values, Types>),
+}
+
+impl<'a, Types, ParseValue> PolyParser<'a, Types, ParseValue>
+where
+    Types: PolyTypes<Types>,
+{
+    pub fn new(symbols: &'a SymbolsProvider<Types>, parse_value: ParseValue) -> Self {
+        PolyParser {
+            symbols,
+            parse_value,
+            unit_type: None,
+        }
+    }
+
+    pub fn unit_type(mut self, unit_type: Types) -> Self {
+        self.unit_type = Some(unit_type);
+        self
+    }
+
+    pub fn parse<Values>(&self, input: &str) -> Result<Polynomial<Values, Types>, ParseError>
+    where
+        Values: PolyValues<Types, Values>,
+        ParseValue: Fn(&str) -> Option<(Values, usize)>,
+    {
+        let mut pos = 0;
+        let polynomial = self.parse_sum(input, &mut pos)?;
+        match next_char(input, &mut pos) {
+            None => Ok(polynomial),
+            Some(_) => Err(ParseError::UnexpectedInput(pos)),
+        }
+    }
+
+    fn parse_sum<Values>(
+        &self,
+        input: &str,
+        pos: &mut usize,
+    ) -> Result<Polynomial<Values, Types>, ParseError>
+    where
+        Values: PolyValues<Types, Values>,
+        ParseValue: Fn(&str) -> Option<(Values, usize)>,
+    {
+        let mut builder = PolynomialBuilder::new();
+        let mut negative = is_operator(input, pos, '-');
+        loop {
+            builder = self.parse_term(input, pos, builder, negative)?;
+            if is_operator(input, pos, '+') {
+                negative = false;
+            } else if is_operator(input, pos, '-') {
+                negative = true;
+            } else {
+                break;
+            }
+        }
+        Ok(builder.build().expect("parsed terms are always built"))
+    }
+
+    fn parse_term<Values>(
+        &self,
+        input: &str,
+        pos: &mut usize,
+        builder: PolynomialBuilder<Values, Types>,
+        negative: bool,
+    ) -> Result<PolynomialBuilder<Values, Types>, ParseError>
+    where
+        Values: PolyValues<Types, Values>,
+        ParseValue: Fn(&str) -> Option<(Values, usize)>,
+    {
+        let start = *pos;
+        let mut items = vec![self.parse_item(input, pos)?];
+        loop {
+            let mut lookahead = *pos;
+            match next_char(input, &mut lookahead) {
+                None | Some('+') | Some(')') => break,
+                Some('-') if is_operator(input, &mut lookahead.clone(), '-') => break,
+                Some('*') => *pos = lookahead + 1,
+                Some(_) => {}
+            }
+            items.push(self.parse_item(input, pos)?);
+        }
+
+        let mut items = items.into_iter().peekable();
+        let coefficient = match items
+            .next_if(|(item, power)| matches!(item, ParsedItem::Value(_)) && *power == 1)
+        {
+            Some((ParsedItem::Value(value), _)) => value,
+            _ => self
+                .unit_type
+                .clone()
+                .and_then(Values::one_with_type)
+                .ok_or(ParseError::MissingCoefficient(start))?,
+        };
+        let coefficient = match negative {
+            true => -coefficient,
+            false => coefficient,
+        };
+        Ok(items
+            .fold(
+                builder.term_builder(coefficient),
+                |term_builder: TermBuilder<Values, Types>, (item, power)| match item {
+                    ParsedItem::Value(value) => term_builder.value(value, power),
+                    ParsedItem::Variable(symbol) => term_builder.variable(symbol, power),
+                    ParsedItem::Polynomial(polynomial) => {
+                        term_builder.polynomial(polynomial, power)
+                    }
+                },
+            )
+            .build())
+    }
+
+    fn parse_item<Values>(
+        &self,
+        input: &str,
+        pos: &mut usize,
+    ) -> Result<(ParsedItem<Values, Types>, u64), ParseError>
+    where
+        Values: PolyValues<Types, Values>,
+        ParseValue: Fn(&str) -> Option<(Values, usize)>,
+    {
+        let item = match next_char(input, pos) {
+            None => return Err(ParseError::UnexpectedEnd),
+            Some('(') => {
+                *pos += 1;
+                let polynomial = self.parse_sum(input, pos)?;
+                match next_char(input, pos) {
+                    Some(')') => *pos += 1,
+                    Some(_) => return Err(ParseError::UnexpectedInput(*pos)),
+                    None => return Err(ParseError::UnexpectedEnd),
+                }
+                ParsedItem::Polynomial(polynomial)
+            }
+            Some(_) => match (self.parse_value)(&input[*pos..]) {
+                Some((value, consumed)) if consumed > 0 => {
+                    *pos += consumed;
+                    ParsedItem::Value(value)
+                }
+                _ => {
+                    let symbol = self
+                        .find_symbol(&input[*pos..])
+                        .ok_or(ParseError::UnexpectedInput(*pos))?;
+                    *pos += symbol.label.len();
+                    ParsedItem::Variable(symbol)
+                }
+            },
+        };
+        Ok((item, parse_power(input, pos)?))
+    }
+
+    // Longest label that is not followed by a continuation of some longer identifier.
+    fn find_symbol(&self, input: &str) -> Option<Symbol<Types>> {
+        self.symbols
+            .symbols()
+            .into_iter()
+            .filter(|symbol| {
+                input.starts_with(symbol.label.as_str())
+                    && !(is_identifier(symbol.label.chars().last())
+                        && is_identifier(input[symbol.label.len()..].chars().next()))
+            })
+            .max_by_key(|symbol| symbol.label.len())
+    }
+}
+
+fn is_identifier(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphabetic() || c.is_ascii_digit() || c == '_')
+}
+
+// Skips whitespaces and returns next character without consuming it.
+fn next_char(input: &str, pos: &mut usize) -> Option<char> {
+    let rest = &input[*pos..];
+    let trimmed = rest.trim_start();
+    *pos += rest.len() - trimmed.len();
+    trimmed.chars().next()
+}
+
+// Binary operator is always surrounded by whitespaces (or starts the sum),
+// this distinguishes `x - 2` from values like `-2`.
+fn is_operator(input: &str, pos: &mut usize, operator: char) -> bool {
+    let mut lookahead = *pos;
+    if next_char(input, &mut lookahead) != Some(operator) {
+        return false;
+    }
+    let after = lookahead + operator.len_utf8();
+    if input[after..].starts_with(char::is_whitespace) {
+        *pos = after;
+        true
+    } else {
+        false
+    }
+}
+
+fn parse_power(input: &str, pos: &mut usize) -> Result<u64, ParseError> {
+    let rest = &input[*pos..];
+    let (digits, consumed) = if let Some(rest) = rest.strip_prefix('^') {
+        let digits = rest
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>();
+        let consumed = 1 + digits.len();
+        (digits, consumed)
+    } else {
+        let superscript = rest
+            .chars()
+            .map_while(|c| SUPERSCRIPT_DIGITS.iter().position(|digit| *digit == c))
+            .collect::<Vec<_>>();
+        if superscript.is_empty() {
+            return Ok(1);
+        }
+        let consumed = superscript
+            .iter()
+            .map(|i| SUPERSCRIPT_DIGITS[*i].len_utf8())
+            .sum();
+        let digits = superscript.iter().map(|i| i.to_string()).collect();
+        (digits, consumed)
+    };
+    let power = digits.parse().map_err(|_| ParseError::InvalidPower(*pos))?;
+    *pos += consumed;
+    Ok(power)
+}
@@ -0,0 +1,451 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::{
+    error::GroebnerError,
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    polynomial_map::PolynomialMap,
+    symbol::{Symbol, SymbolsProvider},
+    term::Term,
+    traits::{Field, IsZero, MonomialOrder, PolyTypes, PolyValues},
+};
+
+impl<Values, Types> PolynomialMap<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero + Field,
+{
+    /// Reduced Gröbner basis (with monic polynomials) of the ideal generated by the components,
+    /// computed with Buchberger's algorithm. The basis can be used for any number of
+    /// `normal_form` and `contains` queries without computing it again.
+    ///
+    /// Components may contain only `variables`, which must have one type closed under
+    /// commutative multiplication, and values of that type. Sub-polynomials are expanded.
+    /// The order of `variables` is the one used by `order`.
+    pub fn groebner<'a, Order>(
+        &self,
+        variables: &'a [Symbol<Types>],
+        order: &'a Order,
+    ) -> Result<GroebnerBasis<'a, Values, Types, Order>, GroebnerError>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        let system = System::new(variables, order)?;
+        let basis = system.basis(self.components())?;
+        Ok(GroebnerBasis {
+            symbols: self.symbols().clone(),
+            system,
+            basis,
+        })
+    }
+
+    /// Polynomials of the reduced Gröbner basis (see `groebner`),
+    /// sorted by their leading monomials in decreasing order.
+    pub fn groebner_basis<Order>(
+        &self,
+        variables: &[Symbol<Types>],
+        order: &Order,
+    ) -> Result<Self, GroebnerError>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        Ok(self.groebner(variables, order)?.to_map())
+    }
+
+    /// Remainder of `poly` modulo the ideal generated by the components (see `GroebnerBasis::normal_form`).
+    /// The basis is computed on every call, `groebner` computes it once for repeated queries.
+    pub fn normal_form<Order>(
+        &self,
+        poly: &Polynomial<Values, Types>,
+        variables: &[Symbol<Types>],
+        order: &Order,
+    ) -> Result<Polynomial<Values, Types>, GroebnerError>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        self.groebner(variables, order)?.normal_form(poly)
+    }
+
+    /// Whether `poly` belongs to the ideal generated by the components (see `GroebnerBasis::contains`).
+    /// The basis is computed on every call, `groebner` computes it once for repeated queries.
+    pub fn ideal_contains<Order>(
+        &self,
+        poly: &Polynomial<Values, Types>,
+        variables: &[Symbol<Types>],
+        order: &Order,
+    ) -> Result<bool, GroebnerError>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        self.groebner(variables, order)?.contains(poly)
+    }
+}
+
+/// Reduced Gröbner basis of an ideal, computed by `PolynomialMap::groebner`.
+pub struct GroebnerBasis<'a, Values, Types, Order: ?Sized> {
+    symbols: SymbolsProvider<Types>,
+    system: System<'a, Values, Types, Order>,
+    basis: Vec<Sparse<Values>>,
+}
+
+impl<Values, Types, Order> GroebnerBasis<'_, Values, Types, Order>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero + Field,
+    Order: MonomialOrder + ?Sized,
+{
+    /// Polynomials of the basis, sorted by their leading monomials in decreasing order.
+    pub fn to_map(&self) -> PolynomialMap<Values, Types> {
+        // the basis contains only variables that appear in the components
+        PolynomialMap::new_unchecked(
+            &self.symbols,
+            self.basis
+                .iter()
+                .map(|poly| self.system.polynomial(poly.clone()))
+                .collect(),
+        )
+    }
+
+    /// Remainder of `poly` modulo the ideal, it does not depend on the generators of the ideal
+    /// because it is reduced by the Gröbner basis.
+    pub fn normal_form(
+        &self,
+        poly: &Polynomial<Values, Types>,
+    ) -> Result<Polynomial<Values, Types>, GroebnerError> {
+        let remainder = self
+            .system
+            .reduce(self.system.sub_poly(poly.sub_poly())?, &self.basis)?;
+        Ok(self.system.polynomial(remainder))
+    }
+
+    /// Whether `poly` belongs to the ideal (see `normal_form`).
+    pub fn contains(&self, poly: &Polynomial<Values, Types>) -> Result<bool, GroebnerError> {
+        let remainder = self
+            .system
+            .reduce(self.system.sub_poly(poly.sub_poly())?, &self.basis)?;
+        Ok(remainder.is_empty())
+    }
+}
+
+// Exponents of every variable.
+type Monomial = Vec<u64>;
+
+// Monomial -> non-zero coefficient.
+type Sparse<Values> = BTreeMap<Monomial, Values>;
+
+struct System<'a, Values, Types, Order: ?Sized> {
+    variables: &'a [Symbol<Types>],
+    order: &'a Order,
+    scalar_type: Types,
+    one: Values,
+}
+
+impl<'a, Values, Types, Order> System<'a, Values, Types, Order>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero + Field,
+    Order: MonomialOrder + ?Sized,
+{
+    fn new(variables: &'a [Symbol<Types>], order: &'a Order) -> Result<Self, GroebnerError> {
+        let symbol_type = |symbol: &Symbol<Types>| {
+            symbol
+                .associated_type
+                .clone()
+                .ok_or_else(|| GroebnerError::UntypedSymbol(symbol.label.clone()))
+        };
+        let scalar_type = symbol_type(variables.first().ok_or(GroebnerError::NoVariables)?)?;
+        for variable in variables {
+            let variable_type = symbol_type(variable)?;
+            if variable_type != scalar_type {
+                return Err(GroebnerError::UnsupportedType(variable_type.to_string()));
+            }
+        }
+        let traits = scalar_type.clone() * scalar_type.clone();
+        if !traits.commutative || traits.result.as_ref() != Some(&scalar_type) {
+            return Err(GroebnerError::NonCommutative(scalar_type.to_string()));
+        }
+        Ok(System {
+            variables,
+            order,
+            one: Values::one_with_type(scalar_type.clone())
+                .ok_or_else(|| GroebnerError::UnsupportedType(scalar_type.to_string()))?,
+            scalar_type,
+        })
+    }
+
+    // arithmetic within a closed type is expected to succeed
+    fn value(&self, value: Option<Values>) -> Result<Values, GroebnerError> {
+        value.ok_or_else(|| GroebnerError::UnsupportedType(self.scalar_type.to_string()))
+    }
+
+    fn compare(&self, lhs: &Monomial, rhs: &Monomial) -> Ordering {
+        self.order.compare(lhs, rhs)
+    }
+
+    fn leading<'p>(&self, poly: &'p Sparse<Values>) -> Option<(&'p Monomial, &'p Values)> {
+        poly.iter()
+            .max_by(|(lhs, _), (rhs, _)| self.compare(lhs, rhs))
+    }
+
+    fn sub_poly(&self, poly: &SubPoly<Values, Types>) -> Result<Sparse<Values>, GroebnerError> {
+        let mut sum = BTreeMap::new();
+        for term in poly.terms() {
+            for (monomial, coefficient) in self.term(term)? {
+                self.add_term(&mut sum, monomial, coefficient)?;
+            }
+        }
+        Ok(sum)
+    }
+
+    fn term(&self, term: &Term<Values, Types>) -> Result<Sparse<Values>, GroebnerError> {
+        let mut product = self.constant(term.coefficient())?;
+        for factor in term.factors() {
+            let base = match factor.factor() {
+                Factor::Value(value) => self.constant(value.value())?,
+                Factor::Variable(variable) => {
+                    let index = self
+                        .variables
+                        .iter()
+                        .position(|known| known == variable.symbol())
+                        .ok_or_else(|| {
+                            GroebnerError::UnknownSymbol(variable.symbol().label.clone())
+                        })?;
+                    let mut monomial = vec![0; self.variables.len()];
+                    monomial[index] = 1;
+                    BTreeMap::from([(monomial, self.one.clone())])
+                }
+                Factor::SubPoly(poly) => self.sub_poly(poly)?,
+            };
+            for _ in 0..factor.power() {
+                product = self.mul(&product, &base)?;
+            }
+        }
+        Ok(product)
+    }
+
+    fn constant(&self, value: &Values) -> Result<Sparse<Values>, GroebnerError> {
+        if value.as_type() != self.scalar_type {
+            return Err(GroebnerError::UnsupportedType(value.as_type().to_string()));
+        }
+        let mut constant = BTreeMap::new();
+        self.add_term(&mut constant, vec![0; self.variables.len()], value.clone())?;
+        Ok(constant)
+    }
+
+    fn polynomial(&self, poly: Sparse<Values>) -> Polynomial<Values, Types> {
+        if poly.is_empty() {
+            return Polynomial::zero(self.scalar_type.clone()).unwrap_or_else(|_| {
+                PolynomialBuilder::new()
+                    .build()
+                    .expect("empty polynomial is always built")
+            });
+        }
+        let mut terms = poly.into_iter().collect::<Vec<_>>();
+        terms.sort_by(|(lhs, _), (rhs, _)| self.compare(rhs, lhs));
+        let mut builder = PolynomialBuilder::new();
+        for (monomial, coefficient) in terms {
+            let mut term_builder = builder.term_builder(coefficient);
+            for (variable, power) in self.variables.iter().zip(monomial) {
+                if power > 0 {
+                    term_builder = term_builder.variable(variable.clone(), power);
+                }
+            }
+            builder = term_builder.build();
+        }
+        builder
+            .build()
+            .expect("terms of a sparse polynomial are always built")
+    }
+
+    fn add_term(
+        &self,
+        poly: &mut Sparse<Values>,
+        monomial: Monomial,
+        coefficient: Values,
+    ) -> Result<(), GroebnerError> {
+        let sum = match poly.remove(&monomial) {
+            Some(known) => self.value(known + coefficient)?,
+            None => coefficient,
+        };
+        if !sum.is_zero() {
+            poly.insert(monomial, sum);
+        }
+        Ok(())
+    }
+
+    fn mul(
+        &self,
+        lhs: &Sparse<Values>,
+        rhs: &Sparse<Values>,
+    ) -> Result<Sparse<Values>, GroebnerError> {
+        let mut product = BTreeMap::new();
+        for (lhs_monomial, lhs_coefficient) in lhs {
+            for (rhs_monomial, rhs_coefficient) in rhs {
+                let coefficient = self.value(lhs_coefficient.clone() * rhs_coefficient.clone())?;
+                self.add_term(
+                    &mut product,
+                    multiply(lhs_monomial, rhs_monomial),
+                    coefficient,
+                )?;
+            }
+        }
+        Ok(product)
+    }
+
+    // `poly - factor·monomial·other`
+    fn sub_multiple(
+        &self,
+        mut poly: Sparse<Values>,
+        factor: &Values,
+        monomial: &Monomial,
+        other: &Sparse<Values>,
+    ) -> Result<Sparse<Values>, GroebnerError> {
+        for (other_monomial, coefficient) in other {
+            let coefficient = self.value(factor.clone() * coefficient.clone())?;
+            self.add_term(&mut poly, multiply(monomial, other_monomial), -coefficient)?;
+        }
+        Ok(poly)
+    }
+
+    fn monic(&self, poly: Sparse<Values>) -> Result<Sparse<Values>, GroebnerError> {
+        let inverse = match self.leading(&poly) {
+            Some((_, leading)) => self.inverse(leading)?,
+            None => return Ok(poly),
+        };
+        poly.into_iter()
+            .map(|(monomial, coefficient)| {
+                Ok((monomial, self.value(coefficient * inverse.clone())?))
+            })
+            .collect()
+    }
+
+    fn inverse(&self, value: &Values) -> Result<Values, GroebnerError> {
+        value
+            .inverse()
+            .ok_or_else(|| GroebnerError::UnsupportedType(self.scalar_type.to_string()))
+    }
+
+    // Full reduction: no monomial of the result is divisible by a leading monomial of `divisors`.
+    fn reduce(
+        &self,
+        mut poly: Sparse<Values>,
+        divisors: &[Sparse<Values>],
+    ) -> Result<Sparse<Values>, GroebnerError> {
+        let mut remainder = BTreeMap::new();
+        while let Some((monomial, coefficient)) = self
+            .leading(&poly)
+            .map(|(monomial, coefficient)| (monomial.clone(), coefficient.clone()))
+        {
+            let divisor = divisors.iter().find_map(|divisor| {
+                let (leading, leading_coefficient) = self.leading(divisor)?;
+                Some((divide(&monomial, leading)?, leading_coefficient, divisor))
+            });
+            match divisor {
+                Some((quotient, leading_coefficient, divisor)) => {
+                    let factor = self.value(coefficient * self.inverse(leading_coefficient)?)?;
+                    poly = self.sub_multiple(poly, &factor, &quotient, divisor)?;
+                    // the leading monomial cancels, it is dropped explicitly in case `is_zero` is approximate
+                    poly.remove(&monomial);
+                }
+                None => {
+                    poly.remove(&monomial);
+                    remainder.insert(monomial, coefficient);
+                }
+            }
+        }
+        Ok(remainder)
+    }
+
+    // Buchberger's algorithm with the coprime leading monomials criterion,
+    // followed by inter-reduction of the result.
+    fn basis(
+        &self,
+        generators: &[Polynomial<Values, Types>],
+    ) -> Result<Vec<Sparse<Values>>, GroebnerError> {
+        let mut basis = vec![];
+        for generator in generators {
+            let generator = self.sub_poly(generator.sub_poly())?;
+            if !generator.is_empty() {
+                basis.push(self.monic(generator)?);
+            }
+        }
+        let mut pairs = (0..basis.len())
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .collect::<Vec<_>>();
+        while let Some((i, j)) = pairs.pop() {
+            let (lhs, _) = self.leading(&basis[i]).expect("basis is non-zero");
+            let (rhs, _) = self.leading(&basis[j]).expect("basis is non-zero");
+            let lcm = lhs
+                .iter()
+                .zip(rhs)
+                .map(|(lhs, rhs)| *lhs.max(rhs))
+                .collect::<Vec<_>>();
+            if lcm == multiply(lhs, rhs) {
+                continue;
+            }
+            // both polynomials are monic
+            let (lhs_quotient, rhs_quotient) = (
+                divide(&lcm, lhs).expect("lcm is a multiple"),
+                divide(&lcm, rhs).expect("lcm is a multiple"),
+            );
+            let s_poly = self.sub_multiple(
+                self.mul(
+                    &BTreeMap::from([(lhs_quotient, self.one.clone())]),
+                    &basis[i],
+                )?,
+                &self.one,
+                &rhs_quotient,
+                &basis[j],
+            )?;
+            let remainder = self.reduce(s_poly, &basis)?;
+            if !remainder.is_empty() {
+                pairs.extend((0..basis.len()).map(|k| (k, basis.len())));
+                basis.push(self.monic(remainder)?);
+            }
+        }
+
+        // polynomials whose leading monomials are divisible by other leading ones are redundant
+        let mut minimal: Vec<Sparse<Values>> = vec![];
+        for (index, poly) in basis.iter().enumerate() {
+            let (leading, _) = self.leading(poly).expect("basis is non-zero");
+            let redundant = basis.iter().enumerate().any(|(other_index, other)| {
+                let (other_leading, _) = self.leading(other).expect("basis is non-zero");
+                other_index != index
+                    && divide(leading, other_leading).is_some()
+                    && (leading != other_leading || other_index < index)
+            });
+            if !redundant {
+                minimal.push(poly.clone());
+            }
+        }
+        let mut reduced = vec![];
+        for index in 0..minimal.len() {
+            let others = minimal
+                .iter()
+                .enumerate()
+                .filter(|(other_index, _)| *other_index != index)
+                .map(|(_, other)| other.clone())
+                .collect::<Vec<_>>();
+            reduced.push(self.reduce(minimal[index].clone(), &others)?);
+        }
+        reduced.sort_by(|lhs, rhs| {
+            let (lhs, _) = self.leading(lhs).expect("basis is non-zero");
+            let (rhs, _) = self.leading(rhs).expect("basis is non-zero");
+            self.compare(rhs, lhs)
+        });
+        Ok(reduced)
+    }
+}
+
+fn multiply(lhs: &Monomial, rhs: &Monomial) -> Monomial {
+    lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs + rhs).collect()
+}
+
+// `None` if `divisor` does not divide `monomial`.
+fn divide(monomial: &Monomial, divisor: &Monomial) -> Option<Monomial> {
+    monomial
+        .iter()
+        .zip(divisor)
+        .map(|(exponent, divisor)| exponent.checked_sub(*divisor))
+        .collect()
+}
@@ -0,0 +1,66 @@
+use crate::{
+    error::IdentityTestError,
+    polynomial::Polynomial,
+    symbol::Symbol,
+    traits::{PolyTypes, PolyValues, Sampler},
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + PartialEq,
+{
+    /// Whether the polynomials are likely equal as functions of their symbols
+    /// (a Schwartz–Zippel style test).
+    ///
+    /// In each of `trials` every symbol of either polynomial is bound to a value drawn from `sampler`
+    /// for its associated type, then both polynomials are evaluated (see `evaluate_batch`) and the results compared.
+    /// `false` is always correct, while `true` may be wrong with a probability
+    /// that decreases with the number of trials and the size of the sampled domain.
+    pub fn probably_equal<S>(
+        &self,
+        other: &Self,
+        sampler: &mut S,
+        trials: usize,
+    ) -> Result<bool, IdentityTestError>
+    where
+        S: Sampler<Values, Types> + ?Sized,
+    {
+        let mut symbols = self.free_symbols();
+        symbols.extend(other.free_symbols());
+        let symbols = symbols.into_iter().collect::<Vec<Symbol<Types>>>();
+        let types = symbols
+            .iter()
+            .map(|symbol| {
+                symbol
+                    .associated_type
+                    .clone()
+                    .ok_or_else(|| IdentityTestError::UntypedSymbol(symbol.label.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bindings = (0..trials)
+            .map(|_| {
+                types
+                    .iter()
+                    .map(|value_type| {
+                        sampler.sample(value_type).ok_or_else(|| {
+                            IdentityTestError::UnsupportedType(value_type.to_string())
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let lhs = self.evaluate_batch(&symbols, bindings.clone());
+        let rhs = other.evaluate_batch(&symbols, bindings);
+        for (lhs, rhs) in lhs.zip(rhs) {
+            let lhs = lhs.map_err(IdentityTestError::TypeError)?;
+            let rhs = rhs.map_err(IdentityTestError::TypeError)?;
+            if lhs != rhs {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    derivative::push,
+    error::{FinalizeError, IntegrationError},
+    factor::{Factor, SubPoly, Value, Variable},
+    polynomial::{Polynomial, PolynomialBuilder},
+    query::degree,
+    symbol::Symbol,
+    term::Term,
+    traits::{DivideByNatural, PolyTypes, PolyValues},
+    visit::PolyVisitor,
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + DivideByNatural,
+{
+    /// Antiderivative by `symbol` without a constant term.
+    ///
+    /// The type of `symbol` must be closed under commutative multiplication and commute
+    /// with every type used in the polynomial. Sub-polynomials that depend on `symbol` are expanded,
+    /// the rest of the factors are kept as they are, then `c·x^n` is integrated to `c/(n+1)·x^(n+1)`
+    /// (`Indivisible` is reported when `DivideByNatural` fails for some `c`).
+    pub fn integrate(&self, symbol: &Symbol<Types>) -> Result<Self, IntegrationError> {
+        let symbol_type = symbol
+            .associated_type
+            .clone()
+            .ok_or_else(|| IntegrationError::UntypedSymbol(symbol.label.clone()))?;
+        let traits = symbol_type.clone() * symbol_type.clone();
+        if !traits.commutative || traits.result.as_ref() != Some(&symbol_type) {
+            return Err(IntegrationError::NonCommutative(symbol_type.to_string()));
+        }
+        let mut checker = CommutationChecker {
+            symbol_type,
+            error: None,
+        };
+        self.visit(&mut checker);
+        if let Some(error) = checker.error {
+            return Err(error);
+        }
+
+        let expansion = Integrator { symbol }.sub_poly(self.sub_poly())?;
+        let mut builder = PolynomialBuilder::new();
+        for (power, parts) in expansion {
+            let power = power
+                .checked_add(1)
+                .ok_or(IntegrationError::PowerOverflow)?;
+            for (coefficient, factors) in parts {
+                let mut term_builder = builder.term_builder(
+                    coefficient
+                        .divide_by_natural(power)
+                        .ok_or(IntegrationError::Indivisible(power))?,
+                );
+                for (factor, factor_power) in &factors {
+                    term_builder = push(term_builder, factor, *factor_power);
+                }
+                builder = term_builder.variable(symbol.clone(), power).build();
+            }
+        }
+        Ok(builder
+            .build()
+            .expect("terms of an existing polynomial are always built"))
+    }
+
+    /// Definite integral by `symbol` from `lower` to `upper`,
+    /// that is the difference of the antiderivative (see `integrate`) at the bounds.
+    pub fn integrate_between(
+        &self,
+        symbol: &Symbol<Types>,
+        lower: Values,
+        upper: Values,
+    ) -> Result<Self, IntegrationError> {
+        let antiderivative = self.integrate(symbol)?;
+        let one = Values::one_with_type(upper.as_type()).ok_or(IntegrationError::TypeError(
+            FinalizeError::NoValueToFinalize,
+        ))?;
+        Ok(Polynomial::builder()
+            .term_builder(one.clone())
+            .polynomial(
+                antiderivative
+                    .clone()
+                    .substitute_value(symbol.clone(), upper),
+                1,
+            )
+            .build()
+            .term_builder(-one)
+            .polynomial(antiderivative.substitute_value(symbol.clone(), lower), 1)
+            .build()
+            .build()
+            .expect("terms of an existing polynomial are always built"))
+    }
+}
+
+struct CommutationChecker<Types> {
+    symbol_type: Types,
+    error: Option<IntegrationError>,
+}
+
+impl<Types> CommutationChecker<Types>
+where
+    Types: PolyTypes<Types>,
+{
+    fn check(&mut self, other: Types) {
+        if self.error.is_none() && !(self.symbol_type.clone() * other.clone()).commutative {
+            self.error = Some(IntegrationError::NonCommutative(other.to_string()));
+        }
+    }
+}
+
+impl<Values, Types> PolyVisitor<Values, Types> for CommutationChecker<Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn pre_term(&mut self, term: &Term<Values, Types>) {
+        self.check(term.coefficient().as_type());
+    }
+
+    fn pre_value(&mut self, value: &Value<Values, Types>) {
+        self.check(value.value().as_type());
+    }
+
+    fn pre_variable(&mut self, variable: &Variable<Types>) {
+        match &variable.symbol().associated_type {
+            Some(symbol_type) => self.check(symbol_type.clone()),
+            None if self.error.is_none() => {
+                self.error = Some(IntegrationError::UntypedSymbol(
+                    variable.symbol().label.clone(),
+                ))
+            }
+            None => {}
+        }
+    }
+}
+
+// Coefficient and factors that do not depend on the symbol, in their original order.
+type Part<Values, Types> = (Values, Vec<(Factor<Values, Types>, u64)>);
+
+// Power of the symbol -> parts multiplied by it.
+type Expansion<Values, Types> = BTreeMap<u64, Vec<Part<Values, Types>>>;
+
+struct Integrator<'a, Types> {
+    symbol: &'a Symbol<Types>,
+}
+
+impl<Types> Integrator<'_, Types>
+where
+    Types: PolyTypes<Types>,
+{
+    fn sub_poly<Values>(
+        &self,
+        poly: &SubPoly<Values, Types>,
+    ) -> Result<Expansion<Values, Types>, IntegrationError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let mut expansion = BTreeMap::new();
+        for term in poly.terms() {
+            for (power, parts) in self.term(term)? {
+                for part in parts {
+                    insert(&mut expansion, power, part);
+                }
+            }
+        }
+        Ok(expansion)
+    }
+
+    fn term<Values>(
+        &self,
+        term: &Term<Values, Types>,
+    ) -> Result<Expansion<Values, Types>, IntegrationError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let counted = |variable: &Variable<Types>| variable.symbol() == self.symbol;
+        let mut expansion: Expansion<Values, Types> =
+            BTreeMap::from([(0, vec![(term.coefficient().clone(), vec![])])]);
+        for factor in term.factors() {
+            expansion = match factor.factor() {
+                Factor::Variable(variable) if counted(variable) => expansion
+                    .into_iter()
+                    .map(|(power, parts)| {
+                        let power = power
+                            .checked_add(factor.power())
+                            .ok_or(IntegrationError::PowerOverflow)?;
+                        Ok((power, parts))
+                    })
+                    .collect::<Result<_, _>>()?,
+                Factor::SubPoly(poly) if factor.power() > 0 && degree(poly, &counted) > 0 => {
+                    let inner = self.sub_poly(poly)?;
+                    (0..factor.power())
+                        .try_fold(expansion, |expansion, _| multiply(expansion, &inner))?
+                }
+                independent => {
+                    for part in expansion.values_mut().flatten() {
+                        part.1.push((independent.clone(), factor.power()));
+                    }
+                    expansion
+                }
+            };
+        }
+        Ok(expansion)
+    }
+}
+
+// Order of factors is preserved, only the symbol is moved,
+// so coefficients of `rhs` become values unless they can be merged with the ones of `lhs`.
+fn multiply<Values, Types>(
+    lhs: Expansion<Values, Types>,
+    rhs: &Expansion<Values, Types>,
+) -> Result<Expansion<Values, Types>, IntegrationError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut product = BTreeMap::new();
+    for (lhs_power, lhs_parts) in &lhs {
+        for (rhs_power, rhs_parts) in rhs {
+            for (lhs_coefficient, lhs_factors) in lhs_parts {
+                for (rhs_coefficient, rhs_factors) in rhs_parts {
+                    let mut factors = lhs_factors.clone();
+                    let coefficient = match lhs_factors.is_empty() {
+                        true => (lhs_coefficient.clone() * rhs_coefficient.clone()).ok_or(
+                            IntegrationError::TypeError(FinalizeError::NoValueToFinalize),
+                        )?,
+                        false => {
+                            if !rhs_coefficient.is_one() {
+                                factors
+                                    .push((Factor::Value(Value::new(rhs_coefficient.clone())), 1));
+                            }
+                            lhs_coefficient.clone()
+                        }
+                    };
+                    factors.extend(rhs_factors.iter().cloned());
+                    let power = lhs_power
+                        .checked_add(*rhs_power)
+                        .ok_or(IntegrationError::PowerOverflow)?;
+                    insert(&mut product, power, (coefficient, factors));
+                }
+            }
+        }
+    }
+    Ok(product)
+}
+
+// Constant parts of the same power are added up, so expanded sub-polynomials have summed coefficients
+// (factors can not be compared, so the other parts are kept separate).
+fn insert<Values, Types>(
+    expansion: &mut Expansion<Values, Types>,
+    power: u64,
+    (coefficient, factors): Part<Values, Types>,
+) where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let parts = expansion.entry(power).or_default();
+    if factors.is_empty() {
+        if let Some(constant) = parts.iter_mut().find(|(_, factors)| factors.is_empty()) {
+            if let Some(sum) = constant.0.clone() + coefficient.clone() {
+                constant.0 = sum;
+                return;
+            }
+        }
+    }
+    parts.push((coefficient, factors));
+}
@@ -0,0 +1,225 @@
+use serde_json::Map;
+
+use crate::{
+    error::JsonError,
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::SymbolsProvider,
+    term::TermBuilder,
+    traits::{PolyTypes, PolyValues},
+};
+
+/// JSON document, used to encode and decode values of a domain for `Polynomial::to_json` and `Polynomial::from_json`.
+pub use serde_json::Value as JsonValue;
+
+/// Version of the schema written by `Polynomial::to_json`, documents of other versions are rejected.
+pub const JSON_SCHEMA_VERSION: u64 = 1;
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// JSON document of the polynomial tree, values are written with `encode`.
+    ///
+    /// Schema (version 1):
+    /// ```text
+    /// document = { "format": "poly-gnom", "version": 1, "polynomial": subpoly }
+    /// subpoly  = { "kind": "subpoly", "terms": [term, ...] }
+    /// term     = { "coefficient": <encoded value>, "factors": [factor, ...] }
+    /// factor   = { "power": <non-negative integer>, "base": node }
+    /// node     = subpoly
+    ///          | { "kind": "value", "value": <encoded value> }
+    ///          | { "kind": "variable", "symbol": <label> }
+    ///          | { "kind": "apply", "function": <name>, "arguments": [subpoly, ...] }
+    /// ```
+    /// `apply` nodes are reserved for function application, they are never written
+    /// and reading them fails with `JsonError::UnsupportedNode`.
+    /// Fields of objects are written in alphabetical order.
+    pub fn to_json<Encode>(&self, encode: Encode) -> String
+    where
+        Encode: Fn(&Values) -> JsonValue,
+    {
+        object([
+            ("format", JsonValue::from("poly-gnom")),
+            ("version", JsonValue::from(JSON_SCHEMA_VERSION)),
+            ("polynomial", sub_poly_to_json(self.sub_poly(), &encode)),
+        ])
+        .to_string()
+    }
+
+    /// Reads a document written by `to_json` (or by other tools following its schema).
+    /// Variables are linked by label to the symbols of `symbols`, values are read with `decode`.
+    ///
+    /// The input must be strict JSON, and nesting deeper than `serde_json` allows (128 levels) is a syntax error.
+    pub fn from_json<Decode>(
+        input: &str,
+        symbols: &SymbolsProvider<Types>,
+        decode: Decode,
+    ) -> Result<Self, JsonError>
+    where
+        Decode: Fn(&JsonValue) -> Option<Values>,
+    {
+        let document =
+            serde_json::from_str::<JsonValue>(input).map_err(|error| JsonError::Syntax {
+                line: error.line(),
+                column: error.column(),
+            })?;
+        if document.get("format").and_then(JsonValue::as_str) != Some("poly-gnom") {
+            return Err(JsonError::InvalidDocument("format".to_owned()));
+        }
+        let version = document
+            .get("version")
+            .and_then(JsonValue::as_u64)
+            .ok_or_else(|| JsonError::InvalidDocument("version".to_owned()))?;
+        if version != JSON_SCHEMA_VERSION {
+            return Err(JsonError::UnsupportedVersion(version));
+        }
+        let polynomial = document
+            .get("polynomial")
+            .ok_or_else(|| JsonError::InvalidDocument("polynomial".to_owned()))?;
+        sub_poly_from_json(polynomial, symbols, &decode)
+    }
+}
+
+fn object<const N: usize>(fields: [(&str, JsonValue); N]) -> JsonValue {
+    JsonValue::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+fn sub_poly_to_json<Values, Types, Encode>(
+    poly: &SubPoly<Values, Types>,
+    encode: &Encode,
+) -> JsonValue
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Encode: Fn(&Values) -> JsonValue,
+{
+    let terms = poly
+        .terms()
+        .iter()
+        .map(|term| {
+            let factors = term
+                .factors()
+                .iter()
+                .map(|factor| {
+                    object([
+                        ("power", JsonValue::from(factor.power())),
+                        ("base", factor_to_json(factor.factor(), encode)),
+                    ])
+                })
+                .collect();
+            object([
+                ("coefficient", encode(term.coefficient())),
+                ("factors", JsonValue::Array(factors)),
+            ])
+        })
+        .collect();
+    object([
+        ("kind", JsonValue::from("subpoly")),
+        ("terms", JsonValue::Array(terms)),
+    ])
+}
+
+fn factor_to_json<Values, Types, Encode>(
+    factor: &Factor<Values, Types>,
+    encode: &Encode,
+) -> JsonValue
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Encode: Fn(&Values) -> JsonValue,
+{
+    match factor {
+        Factor::Value(value) => object([
+            ("kind", JsonValue::from("value")),
+            ("value", encode(value.value())),
+        ]),
+        Factor::Variable(variable) => object([
+            ("kind", JsonValue::from("variable")),
+            ("symbol", JsonValue::from(variable.symbol().label.clone())),
+        ]),
+        Factor::SubPoly(poly) => sub_poly_to_json(poly, encode),
+    }
+}
+
+fn field<'a>(node: &'a JsonValue, key: &str) -> Result<&'a JsonValue, JsonError> {
+    node.get(key)
+        .ok_or_else(|| JsonError::InvalidDocument(key.to_owned()))
+}
+
+fn sub_poly_from_json<Values, Types, Decode>(
+    node: &JsonValue,
+    symbols: &SymbolsProvider<Types>,
+    decode: &Decode,
+) -> Result<Polynomial<Values, Types>, JsonError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Decode: Fn(&JsonValue) -> Option<Values>,
+{
+    if field(node, "kind")?.as_str() != Some("subpoly") {
+        return Err(JsonError::InvalidDocument("kind".to_owned()));
+    }
+    let terms = field(node, "terms")?
+        .as_array()
+        .ok_or_else(|| JsonError::InvalidDocument("terms".to_owned()))?;
+    let mut builder = PolynomialBuilder::new();
+    for term in terms {
+        let coefficient = decode(field(term, "coefficient")?).ok_or(JsonError::InvalidValue)?;
+        let factors = field(term, "factors")?
+            .as_array()
+            .ok_or_else(|| JsonError::InvalidDocument("factors".to_owned()))?;
+        builder = factors
+            .iter()
+            .try_fold(builder.term_builder(coefficient), |term_builder, factor| {
+                factor_from_json(term_builder, factor, symbols, decode)
+            })?
+            .build();
+    }
+    builder
+        .build()
+        .map_err(|_| JsonError::InvalidDocument("terms".to_owned()))
+}
+
+fn factor_from_json<Values, Types, Decode>(
+    term_builder: TermBuilder<Values, Types>,
+    factor: &JsonValue,
+    symbols: &SymbolsProvider<Types>,
+    decode: &Decode,
+) -> Result<TermBuilder<Values, Types>, JsonError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Decode: Fn(&JsonValue) -> Option<Values>,
+{
+    let power = field(factor, "power")?
+        .as_u64()
+        .ok_or_else(|| JsonError::InvalidDocument("power".to_owned()))?;
+    let base = field(factor, "base")?;
+    match field(base, "kind")?.as_str() {
+        Some("value") => {
+            let value = decode(field(base, "value")?).ok_or(JsonError::InvalidValue)?;
+            Ok(term_builder.value(value, power))
+        }
+        Some("variable") => {
+            let label = field(base, "symbol")?
+                .as_str()
+                .ok_or_else(|| JsonError::InvalidDocument("symbol".to_owned()))?;
+            let symbol = symbols
+                .get(label)
+                .ok_or_else(|| JsonError::UnknownSymbol(label.to_owned()))?;
+            Ok(term_builder.variable(symbol, power))
+        }
+        Some("subpoly") => {
+            Ok(term_builder.polynomial(sub_poly_from_json(base, symbols, decode)?, power))
+        }
+        Some("apply") => Err(JsonError::UnsupportedNode("apply".to_owned())),
+        _ => Err(JsonError::InvalidDocument("kind".to_owned())),
+    }
+}
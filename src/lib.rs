@@ -1,7 +1,30 @@
+pub mod codegen;
+pub mod error;
+pub mod factor;
+pub mod format;
+pub mod groebner;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod polynomial;
+pub mod polynomial_map;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod symbol;
+pub mod term;
 pub mod traits;
+pub mod visit;
 
-mod error;
-mod factor;
-mod term;
+mod batch;
+mod cas;
+mod derivative;
+mod domain;
+mod dot;
+mod equality;
+mod identity;
+mod integral;
+mod lowering;
+mod order;
+mod power;
+mod query;
+mod sync;
+mod univariate;
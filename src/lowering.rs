@@ -0,0 +1,502 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    error::{CodegenError, FinalizeError},
+    factor::{Factor, Finalizable, SubPoly},
+    polynomial::Polynomial,
+    power::power_chain,
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    traits::{PolyTypes, PolyValues, PowerStrategy},
+};
+
+// Typed straight-line form of a polynomial that code generators print.
+// Every instruction is computed once: equal instructions of the same type are merged (operands of commutative
+// products are ordered first), so common subexpressions end up in a single temporary.
+// Products and sums are done in the same order as `Polynomial::as_value` does them,
+// except that multiplications by unit coefficients are left out.
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Op {
+    // index of the argument, arguments are always the first instructions
+    Argument(usize),
+    // constant as it is printed by the code generator
+    Constant(String),
+    Mul(usize, usize),
+    Add(usize, usize),
+}
+
+pub struct Instruction<Types> {
+    pub op: Op,
+    pub value_type: Types,
+}
+
+pub struct Lowered<Types> {
+    pub instructions: Vec<Instruction<Types>>,
+    pub result: usize,
+}
+
+impl<Types> Lowered<Types> {
+    // Number of instructions that use each one as an operand (the result is counted as a use too).
+    pub fn uses(&self) -> Vec<usize> {
+        let mut uses = vec![0; self.instructions.len()];
+        uses[self.result] += 1;
+        for instruction in &self.instructions {
+            if let Op::Mul(lhs, rhs) | Op::Add(lhs, rhs) = instruction.op {
+                uses[lhs] += 1;
+                uses[rhs] += 1;
+            }
+        }
+        uses
+    }
+}
+
+pub fn lower<Values, Types, Constant>(
+    polynomial: &Polynomial<Values, Types>,
+    arguments: &[Symbol<Types>],
+    constant: Constant,
+) -> Result<Lowered<Types>, CodegenError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Constant: Fn(&Values) -> String,
+{
+    let mut lowerer = Lowerer::new(arguments, constant)?;
+    // constant parts are computed here once instead of in the generated code
+    let folded = polynomial.clone().fold_constants();
+    let result = lowerer.sub_poly(folded.sub_poly())?;
+    Ok(Lowered {
+        instructions: lowerer.instructions,
+        result,
+    })
+}
+
+struct Lowerer<'a, Types, Constant> {
+    arguments: &'a [Symbol<Types>],
+    constant: Constant,
+    instructions: Vec<Instruction<Types>>,
+    // instructions with the given op, constants of different types can be printed the same way
+    known: HashMap<Op, Vec<usize>>,
+}
+
+impl<'a, Types, Constant> Lowerer<'a, Types, Constant>
+where
+    Types: PolyTypes<Types>,
+{
+    fn new(arguments: &'a [Symbol<Types>], constant: Constant) -> Result<Self, CodegenError> {
+        let mut lowerer = Lowerer {
+            arguments,
+            constant,
+            instructions: vec![],
+            known: HashMap::new(),
+        };
+        for (index, argument) in arguments.iter().enumerate() {
+            let value_type = argument
+                .associated_type
+                .clone()
+                .ok_or_else(|| CodegenError::UntypedSymbol(argument.label.clone()))?;
+            lowerer.push(Op::Argument(index), value_type);
+        }
+        Ok(lowerer)
+    }
+
+    fn push(&mut self, op: Op, value_type: Types) -> usize {
+        let known = self.known.entry(op.clone()).or_default();
+        if let Some(index) = known
+            .iter()
+            .find(|index| self.instructions[**index].value_type == value_type)
+        {
+            return *index;
+        }
+        known.push(self.instructions.len());
+        self.instructions.push(Instruction { op, value_type });
+        self.instructions.len() - 1
+    }
+
+    fn value_type(&self, index: usize) -> Types {
+        self.instructions[index].value_type.clone()
+    }
+
+    fn mul(&mut self, lhs: usize, rhs: usize) -> Result<usize, CodegenError> {
+        let traits = self.value_type(lhs) * self.value_type(rhs);
+        let value_type = traits
+            .result
+            .ok_or(CodegenError::TypeError(FinalizeError::NoTypeToFinalize))?;
+        let op = if traits.commutative {
+            Op::Mul(lhs.min(rhs), lhs.max(rhs))
+        } else {
+            Op::Mul(lhs, rhs)
+        };
+        Ok(self.push(op, value_type))
+    }
+
+    fn add(&mut self, lhs: usize, rhs: usize) -> Result<usize, CodegenError> {
+        let value_type = (self.value_type(lhs) + self.value_type(rhs))
+            .ok_or(CodegenError::TypeError(FinalizeError::NoTypeToFinalize))?;
+        Ok(self.push(Op::Add(lhs, rhs), value_type))
+    }
+
+    fn sub_poly<Values>(&mut self, poly: &SubPoly<Values, Types>) -> Result<usize, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        let mut sum = None;
+        for term in poly.terms() {
+            let term = self.term(term)?;
+            sum = Some(match sum {
+                None => term,
+                Some(sum) => self.add(sum, term)?,
+            });
+        }
+        sum.ok_or(CodegenError::TypeError(FinalizeError::NoTypeToFinalize))
+    }
+
+    fn term<Values>(&mut self, term: &Term<Values, Types>) -> Result<usize, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        let mut monomial = None;
+        for factor in term.factors() {
+            let factor = self.monomial_factor(factor)?;
+            monomial = Some(match monomial {
+                None => factor,
+                Some(monomial) => self.mul(monomial, factor)?,
+            });
+        }
+        let coefficient = term.coefficient();
+        match monomial {
+            // a unit does not change the value, as long as it does not change the type either
+            Some(monomial) if coefficient.is_one() && self.keeps_type(coefficient, monomial) => {
+                Ok(monomial)
+            }
+            Some(monomial) => {
+                let coefficient = self.value(coefficient);
+                self.mul(coefficient, monomial)
+            }
+            None => Ok(self.value(coefficient)),
+        }
+    }
+
+    fn keeps_type<Values>(&self, coefficient: &Values, monomial: usize) -> bool
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let monomial_type = self.value_type(monomial);
+        (coefficient.as_type() * monomial_type.clone()).result == Some(monomial_type)
+    }
+
+    fn monomial_factor<Values>(
+        &mut self,
+        factor: &MonomialFactor<Values, Types>,
+    ) -> Result<usize, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        if factor.power() == 0 {
+            let value_type = factor.finalize_type().map_err(CodegenError::TypeError)?;
+            let one = Values::one_with_type(value_type)
+                .ok_or(CodegenError::TypeError(FinalizeError::NoValueToFinalize))?;
+            return Ok(self.value(&one));
+        }
+        let base = self.factor(factor.factor())?;
+        let strategy = self.value_type(base).power_strategy();
+        let mut chain = vec![base];
+        for (lhs, rhs) in power_chain(factor.power(), strategy) {
+            let next = self.mul(chain[lhs], chain[rhs])?;
+            chain.push(next);
+        }
+        Ok(*chain.last().unwrap())
+    }
+
+    fn factor<Values>(&mut self, factor: &Factor<Values, Types>) -> Result<usize, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        match factor {
+            Factor::Value(value) => Ok(self.value(value.value())),
+            Factor::Variable(variable) => self
+                .arguments
+                .iter()
+                .position(|argument| argument == variable.symbol())
+                .ok_or_else(|| CodegenError::UnboundSymbol(variable.symbol().label.clone())),
+            Factor::SubPoly(poly) => self.sub_poly(poly),
+        }
+    }
+
+    fn value<Values>(&mut self, value: &Values) -> usize
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        let constant = (self.constant)(value);
+        self.push(Op::Constant(constant), value.as_type())
+    }
+}
+
+// Horner pass of the `Lowerer` for backends of scalar domains (a single type with commutative multiplication):
+// the polynomial is expanded into a sum of monomials and evaluated with the Horner scheme,
+// nested in the order of `arguments`. Instructions are typed and merged the same way as by `lower`,
+// every argument and every instruction must have the type of the polynomial.
+pub fn lower_horner<Values, Types, Constant>(
+    polynomial: &Polynomial<Values, Types>,
+    arguments: &[Symbol<Types>],
+    constant: Constant,
+) -> Result<Lowered<Types>, CodegenError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    Constant: Fn(&Values) -> String,
+{
+    let scalar_type = polynomial.as_type().map_err(CodegenError::TypeError)?;
+    let traits = scalar_type.clone() * scalar_type.clone();
+    if !traits.commutative || traits.result.as_ref() != Some(&scalar_type) {
+        return Err(CodegenError::UnsupportedType(scalar_type.to_string()));
+    }
+    let mut lowerer = Lowerer::new(arguments, constant)?;
+    let expander = Expander {
+        arguments,
+        scalar_type,
+    };
+    let expanded = expander.sub_poly(polynomial.sub_poly())?;
+    let result = lowerer.horner(expanded.into_iter().collect(), 0, &expander.scalar_type)?;
+    let result = lowerer.unit_or_value::<Values>(result, &expander.scalar_type)?;
+    if let Some(instruction) = lowerer
+        .instructions
+        .iter()
+        .find(|instruction| instruction.value_type != expander.scalar_type)
+    {
+        return Err(CodegenError::UnsupportedType(
+            instruction.value_type.to_string(),
+        ));
+    }
+    Ok(Lowered {
+        instructions: lowerer.instructions,
+        result,
+    })
+}
+
+// Exponents of every argument -> coefficient of the monomial.
+type Expanded<Values> = BTreeMap<Vec<u64>, Values>;
+
+struct Expander<'a, Types> {
+    arguments: &'a [Symbol<Types>],
+    scalar_type: Types,
+}
+
+impl<Types> Expander<'_, Types>
+where
+    Types: PolyTypes<Types>,
+{
+    fn one<Values>(&self) -> Result<Values, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        Values::one_with_type(self.scalar_type.clone())
+            .ok_or(CodegenError::TypeError(FinalizeError::NoValueToFinalize))
+    }
+
+    fn constant<Values>(&self, value: &Values) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        if value.as_type() != self.scalar_type {
+            return Err(CodegenError::UnsupportedType(value.as_type().to_string()));
+        }
+        Ok(BTreeMap::from([(
+            vec![0; self.arguments.len()],
+            value.clone(),
+        )]))
+    }
+
+    fn add<Values>(
+        &self,
+        mut lhs: Expanded<Values>,
+        rhs: Expanded<Values>,
+    ) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        for (exponents, coefficient) in rhs {
+            let sum = match lhs.remove(&exponents) {
+                Some(known) => (known + coefficient)
+                    .ok_or(CodegenError::TypeError(FinalizeError::NoValueToFinalize))?,
+                None => coefficient,
+            };
+            lhs.insert(exponents, sum);
+        }
+        Ok(lhs)
+    }
+
+    fn mul<Values>(
+        &self,
+        lhs: &Expanded<Values>,
+        rhs: &Expanded<Values>,
+    ) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let mut product = BTreeMap::new();
+        for (lhs_exponents, lhs_coefficient) in lhs {
+            for (rhs_exponents, rhs_coefficient) in rhs {
+                let exponents = lhs_exponents
+                    .iter()
+                    .zip(rhs_exponents)
+                    .map(|(lhs, rhs)| lhs + rhs)
+                    .collect();
+                let coefficient = (lhs_coefficient.clone() * rhs_coefficient.clone())
+                    .ok_or(CodegenError::TypeError(FinalizeError::NoValueToFinalize))?;
+                product = self.add(product, BTreeMap::from([(exponents, coefficient)]))?;
+            }
+        }
+        Ok(product)
+    }
+
+    fn sub_poly<Values>(
+        &self,
+        poly: &SubPoly<Values, Types>,
+    ) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        poly.terms()
+            .iter()
+            .try_fold(BTreeMap::new(), |sum, term| self.add(sum, self.term(term)?))
+    }
+
+    fn term<Values>(&self, term: &Term<Values, Types>) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        term.factors()
+            .iter()
+            .try_fold(self.constant(term.coefficient())?, |product, factor| {
+                self.mul(&product, &self.monomial_factor(factor)?)
+            })
+    }
+
+    fn monomial_factor<Values>(
+        &self,
+        factor: &MonomialFactor<Values, Types>,
+    ) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let base = self.factor(factor.factor())?;
+        (0..factor.power()).try_fold(self.constant(&self.one()?)?, |power, _| {
+            self.mul(&power, &base)
+        })
+    }
+
+    fn factor<Values>(
+        &self,
+        factor: &Factor<Values, Types>,
+    ) -> Result<Expanded<Values>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        match factor {
+            Factor::Value(value) => self.constant(value.value()),
+            Factor::Variable(variable) => {
+                let index = self
+                    .arguments
+                    .iter()
+                    .position(|argument| argument == variable.symbol())
+                    .ok_or_else(|| CodegenError::UnboundSymbol(variable.symbol().label.clone()))?;
+                let mut exponents = vec![0; self.arguments.len()];
+                exponents[index] = 1;
+                Ok(BTreeMap::from([(exponents, self.one()?)]))
+            }
+            Factor::SubPoly(poly) => self.sub_poly(poly),
+        }
+    }
+}
+
+impl<Types, Constant> Lowerer<'_, Types, Constant>
+where
+    Types: PolyTypes<Types>,
+{
+    // `None` stands for a unit coefficient, so multiplications by it are not emitted.
+    fn horner<Values>(
+        &mut self,
+        monomials: Vec<(Vec<u64>, Values)>,
+        variable: usize,
+        scalar_type: &Types,
+    ) -> Result<Option<usize>, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        if variable == self.arguments.len() {
+            // exponents are unique, so only one monomial is left here
+            let (_, coefficient) = monomials.into_iter().next().unwrap();
+            return Ok((!coefficient.is_one()).then(|| self.value(&coefficient)));
+        }
+        let mut groups: BTreeMap<u64, Vec<(Vec<u64>, Values)>> = BTreeMap::new();
+        for (exponents, coefficient) in monomials {
+            groups
+                .entry(exponents[variable])
+                .or_default()
+                .push((exponents, coefficient));
+        }
+        let mut result: Option<Option<usize>> = None;
+        let mut previous = 0;
+        for (exponent, group) in groups.into_iter().rev() {
+            let inner = self.horner(group, variable + 1, scalar_type)?;
+            result = Some(match result {
+                None => inner,
+                Some(result) => {
+                    let shifted = self.mul_power(result, variable, previous - exponent)?;
+                    let shifted = self.unit_or_value::<Values>(shifted, scalar_type)?;
+                    let inner = self.unit_or_value::<Values>(inner, scalar_type)?;
+                    Some(self.add(shifted, inner)?)
+                }
+            });
+            previous = exponent;
+        }
+        self.mul_power(result.unwrap(), variable, previous)
+    }
+
+    fn mul_power(
+        &mut self,
+        lhs: Option<usize>,
+        variable: usize,
+        power: u64,
+    ) -> Result<Option<usize>, CodegenError> {
+        if power == 0 {
+            return Ok(lhs);
+        }
+        let mut chain = vec![variable];
+        for (chain_lhs, chain_rhs) in power_chain(power, PowerStrategy::Binary) {
+            let next = self.mul(chain[chain_lhs], chain[chain_rhs])?;
+            chain.push(next);
+        }
+        let power = *chain.last().unwrap();
+        match lhs {
+            None => Ok(Some(power)),
+            Some(lhs) => Ok(Some(self.mul(lhs, power)?)),
+        }
+    }
+
+    fn unit_or_value<Values>(
+        &mut self,
+        index: Option<usize>,
+        scalar_type: &Types,
+    ) -> Result<usize, CodegenError>
+    where
+        Values: PolyValues<Types, Values>,
+        Constant: Fn(&Values) -> String,
+    {
+        match index {
+            Some(index) => Ok(index),
+            None => {
+                let one = Values::one_with_type(scalar_type.clone())
+                    .ok_or(CodegenError::TypeError(FinalizeError::NoValueToFinalize))?;
+                Ok(self.value(&one))
+            }
+        }
+    }
+}
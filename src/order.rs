@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use crate::{
+    factor::{Factor, Finalizable, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    traits::{MonomialOrder, PolyTypes, PolyValues},
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Sorts terms of the polynomial and of every sub-polynomial in decreasing `order`
+    /// of the monomials formed by their variables (values and sub-polynomials are not part of them).
+    ///
+    /// Monomials are commutative when all factors of their term commute with each other (per `MulTraits`),
+    /// then factors are also reordered: values first, then variables by symbol, then sub-polynomials.
+    /// Symbols are ordered as `Symbol`s are (by id first), and if some term of a sum is not commutative,
+    /// the whole sum is compared as words (see `MonomialOrder::compare_words`).
+    /// Terms with equal monomials keep their relative order.
+    pub fn sort_terms<Order>(self, order: &Order) -> Self
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        self.ordering(order).sub_poly(self.sub_poly())
+    }
+
+    /// Greatest top level term in `order` (see `sort_terms`), the first one if there are several.
+    pub fn leading_term<Order>(&self, order: &Order) -> Option<&Term<Values, Types>>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        let ordering = self.ordering(order);
+        let monomials = ordering.monomials(self.sub_poly());
+        let mut leading = None::<usize>;
+        for index in 0..monomials.len() {
+            if leading.is_none_or(|leading| {
+                ordering.compare(&monomials, index, leading) == Ordering::Greater
+            }) {
+                leading = Some(index);
+            }
+        }
+        leading.map(|index| &self.terms()[index])
+    }
+
+    /// Coefficient of `leading_term`.
+    pub fn leading_coefficient<Order>(&self, order: &Order) -> Option<&Values>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        self.leading_term(order).map(Term::coefficient)
+    }
+
+    /// Factors of `leading_term`.
+    pub fn leading_monomial<Order>(&self, order: &Order) -> Option<&[MonomialFactor<Values, Types>]>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        self.leading_term(order).map(Term::factors)
+    }
+
+    fn ordering<'a, Order>(&self, order: &'a Order) -> TermOrdering<'a, Types, Order>
+    where
+        Order: MonomialOrder + ?Sized,
+    {
+        TermOrdering {
+            symbols: self.free_symbols().into_iter().collect(),
+            order,
+        }
+    }
+}
+
+// Monomials of all terms of a sum, words are used only if some term is not commutative.
+enum Monomials {
+    Commutative(Vec<Vec<u64>>),
+    Words(Vec<Vec<(usize, u64)>>),
+}
+
+impl Monomials {
+    fn len(&self) -> usize {
+        match self {
+            Monomials::Commutative(monomials) => monomials.len(),
+            Monomials::Words(words) => words.len(),
+        }
+    }
+}
+
+struct TermOrdering<'a, Types, Order: ?Sized> {
+    // sorted, positions in it are the letters of words and the indices of exponents
+    symbols: Vec<Symbol<Types>>,
+    order: &'a Order,
+}
+
+impl<Types, Order> TermOrdering<'_, Types, Order>
+where
+    Types: PolyTypes<Types>,
+    Order: MonomialOrder + ?Sized,
+{
+    fn compare(&self, monomials: &Monomials, lhs: usize, rhs: usize) -> Ordering {
+        match monomials {
+            Monomials::Commutative(monomials) => {
+                self.order.compare(&monomials[lhs], &monomials[rhs])
+            }
+            Monomials::Words(words) => self.order.compare_words(&words[lhs], &words[rhs]),
+        }
+    }
+
+    fn monomials<Values>(&self, poly: &SubPoly<Values, Types>) -> Monomials
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        match poly.terms().iter().all(is_commutative) {
+            true => Monomials::Commutative(
+                poly.terms()
+                    .iter()
+                    .map(|term| self.exponents(term))
+                    .collect(),
+            ),
+            false => Monomials::Words(poly.terms().iter().map(|term| self.word(term)).collect()),
+        }
+    }
+
+    // Symbols of different providers may share ids, so they are identified by their position in `symbols`.
+    fn index(&self, symbol: &Symbol<Types>) -> usize {
+        self.symbols
+            .binary_search(symbol)
+            .expect("symbols of the polynomial are collected")
+    }
+
+    fn exponents<Values>(&self, term: &Term<Values, Types>) -> Vec<u64> {
+        let mut exponents = vec![0; self.symbols.len()];
+        for factor in term.factors() {
+            if let Factor::Variable(variable) = factor.factor() {
+                exponents[self.index(variable.symbol())] += factor.power();
+            }
+        }
+        exponents
+    }
+
+    // Run-length encoded word of the variables of the term, powers are never expanded.
+    // Adjacent factors of the same symbol stay separate runs, `compare_words` does not need maximal ones.
+    fn word<Values>(&self, term: &Term<Values, Types>) -> Vec<(usize, u64)> {
+        term.factors()
+            .iter()
+            .filter(|factor| factor.power() > 0)
+            .filter_map(|factor| match factor.factor() {
+                Factor::Variable(variable) => Some((self.index(variable.symbol()), factor.power())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn sub_poly<Values>(&self, poly: &SubPoly<Values, Types>) -> Polynomial<Values, Types>
+    where
+        Values: PolyValues<Types, Values>,
+    {
+        let monomials = self.monomials(poly);
+        let mut indices = (0..poly.terms().len()).collect::<Vec<_>>();
+        // stable, so equal monomials keep their order
+        indices.sort_by(|lhs, rhs| self.compare(&monomials, *rhs, *lhs));
+
+        let mut builder = PolynomialBuilder::new();
+        for index in indices {
+            let term = &poly.terms()[index];
+            let mut factors = term.factors().iter().collect::<Vec<_>>();
+            if is_commutative(term) {
+                factors.sort_by_key(|factor| match factor.factor() {
+                    Factor::Value(_) => (0, None),
+                    Factor::Variable(variable) => (1, Some(self.index(variable.symbol()))),
+                    Factor::SubPoly(_) => (2, None),
+                });
+            }
+            let mut term_builder = builder.term_builder(term.coefficient().clone());
+            for factor in factors {
+                term_builder = match factor.factor() {
+                    Factor::Value(value) => {
+                        term_builder.value(value.value().clone(), factor.power())
+                    }
+                    Factor::Variable(variable) => {
+                        term_builder.variable(variable.symbol().clone(), factor.power())
+                    }
+                    Factor::SubPoly(poly) => {
+                        term_builder.polynomial(self.sub_poly(poly), factor.power())
+                    }
+                };
+            }
+            builder = term_builder.build();
+        }
+        builder
+            .build()
+            .expect("terms of an existing polynomial are always built")
+    }
+}
+
+// Whether every pair of factors commutes, so they can be reordered.
+fn is_commutative<Values, Types>(term: &Term<Values, Types>) -> bool
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let types = term
+        .factors()
+        .iter()
+        .map(|factor| factor.factor().finalize_type())
+        .collect::<Result<Vec<_>, _>>();
+    let Ok(types) = types else {
+        return false;
+    };
+    types.iter().enumerate().all(|(index, lhs)| {
+        types[index + 1..]
+            .iter()
+            .all(|rhs| (lhs.clone() * rhs.clone()).commutative)
+    })
+}
@@ -1,11 +1,14 @@
 use std::fmt::{Debug, Display};
 
 use crate::{
+    batch::BatchPlan,
+    cas::CasWriter,
+    dot::DotWriter,
     error::{BuilderError, FinalizeError},
-    factor::{Factor, Finalizable, SubPoly, Substitutiable, Value, Variable},
+    factor::{Factor, Finalizable, Foldable, SubPoly, Substitutiable, ToLatex, Value, Variable},
     symbol::Symbol,
     term::{Term, TermBuilder},
-    traits::{PolyTypes, PolyValues},
+    traits::{CasSyntax, PolyTypes, PolyValues},
 };
 
 #[derive(Clone)]
@@ -13,9 +16,26 @@ pub struct Polynomial<Values, Types> {
     poly: SubPoly<Values, Types>,
 }
 
-impl<Values, Types> Into<SubPoly<Values, Types>> for Polynomial<Values, Types> {
-    fn into(self) -> SubPoly<Values, Types> {
-        self.poly
+impl<Values, Types> From<Polynomial<Values, Types>> for SubPoly<Values, Types> {
+    fn from(polynomial: Polynomial<Values, Types>) -> Self {
+        polynomial.poly
+    }
+}
+
+impl<Values, Types> From<SubPoly<Values, Types>> for Polynomial<Values, Types> {
+    fn from(poly: SubPoly<Values, Types>) -> Self {
+        Polynomial { poly }
+    }
+}
+
+impl<Values, Types> Polynomial<Values, Types> {
+    pub(crate) fn sub_poly(&self) -> &SubPoly<Values, Types> {
+        &self.poly
+    }
+
+    /// Top level terms of the polynomial, the rest of the tree is reachable through their factors.
+    pub fn terms(&self) -> &[Term<Values, Types>] {
+        self.poly.terms()
     }
 }
 
@@ -69,6 +89,91 @@ where
     pub fn as_value(self) -> Result<Values, FinalizeError> {
         self.poly.finalize_value()
     }
+
+    /// Renders the polynomial as LaTeX math (without surrounding `$`).
+    /// Values and symbols are rendered with `PolyValues::to_latex` and `SymbolInfo::to_latex`,
+    /// unit coefficients (see `PolyValues::is_one`) are omitted.
+    pub fn to_latex(&self) -> String {
+        self.poly.to_latex()
+    }
+
+    /// Graphviz description of the polynomial tree (the same structure as in README's `Poly-tree.svg`).
+    /// Every node is labelled with its kind, coefficient, power or symbol, and the inferred type (or the typing error).
+    pub fn to_dot(&self) -> String {
+        let mut writer = DotWriter::new();
+        writer.sub_poly(None, &self.poly);
+        writer.finish()
+    }
+
+    /// Python snippet for SymPy that declares every symbol of the polynomial and assigns the polynomial to `expr`.
+    /// Symbols whose type does not commute with some type used in the polynomial are declared with `commutative=False`.
+    pub fn to_sympy(&self) -> String {
+        CasWriter::new(CasSyntax::SymPy).sympy_script(&self.poly)
+    }
+
+    /// Maxima expression, non-commutative products and powers are written with `.` and `^^`.
+    pub fn to_maxima(&self) -> String {
+        CasWriter::new(CasSyntax::Maxima).sub_poly(&self.poly)
+    }
+
+    /// Mathematica expression, non-commutative products are written with `**` (`NonCommutativeMultiply`)
+    /// and their powers are expanded.
+    pub fn to_mathematica(&self) -> String {
+        CasWriter::new(CasSyntax::Mathematica).sub_poly(&self.poly)
+    }
+
+    /// Evaluates every part of the polynomial that has no variables in it
+    /// and merges adjacent constant factors of each term,
+    /// so the result contains only the remaining symbols and values that can not be simplified further.
+    pub fn fold_constants(self) -> Self {
+        match self.poly.fold_constants() {
+            Factor::Value(value) => Self::builder()
+                .term_builder(value.value().clone())
+                .build()
+                .build()
+                .expect("constant term is always built"),
+            Factor::Variable(_) => panic!(),
+            Factor::SubPoly(poly) => Polynomial::<Values, Types> { poly },
+        }
+    }
+
+    /// Evaluates the polynomial once for every set of values in `bindings`,
+    /// where `i`-th value of each set is substituted in place of `symbols[i]`.
+    ///
+    /// The polynomial is prepared once, before the first binding: every part that does not depend on `symbols`
+    /// is evaluated, and the variables of `symbols` are resolved to positions in a binding,
+    /// so all of them are substituted in a single pass.
+    /// If all `symbols` have associated types, the polynomial is typed once up front as well
+    /// and each binding is only checked against the types of `symbols`,
+    /// so a polynomial that can not be typed fails every binding.
+    /// Results are produced lazily, one per binding; bindings of a wrong length yield `MismatchedBinding`.
+    pub fn evaluate_batch<Bindings>(
+        &self,
+        symbols: &[Symbol<Types>],
+        bindings: Bindings,
+    ) -> impl Iterator<Item = Result<Values, FinalizeError>>
+    where
+        Bindings: IntoIterator<Item = Vec<Values>>,
+    {
+        let symbols = symbols.to_vec();
+        let plan = BatchPlan::new(self, &symbols);
+
+        bindings.into_iter().map(move |values| {
+            if values.len() != symbols.len() {
+                return Err(FinalizeError::MismatchedBinding);
+            }
+            symbols
+                .iter()
+                .zip(values.iter())
+                .try_for_each(|(symbol, value)| match &symbol.associated_type {
+                    Some(associated_type) if *associated_type != value.as_type() => {
+                        Err(FinalizeError::MismatchedTypes)
+                    }
+                    _ => Ok(()),
+                })?;
+            plan.evaluate::<Types>(&values)
+        })
+    }
 }
 
 impl<Values, Types> Substitutiable<Values, Types> for Polynomial<Values, Types>
@@ -125,6 +230,12 @@ pub struct PolynomialBuilder<Values, Types> {
     parts: Vec<Result<Term<Values, Types>, BuilderError>>,
 }
 
+impl<Values, Types> Default for PolynomialBuilder<Values, Types> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<Values, Types> PolynomialBuilder<Values, Types> {
     pub fn new() -> Self {
         PolynomialBuilder { parts: Vec::new() }
@@ -134,10 +245,7 @@ impl<Values, Types> PolynomialBuilder<Values, Types> {
         TermBuilder::new(self, coefficient)
     }
 
-    pub fn maybe_term(
-        mut self,
-        term: Result<Term<Values, Types>, BuilderError>,
-    ) -> Self {
+    pub fn maybe_term(mut self, term: Result<Term<Values, Types>, BuilderError>) -> Self {
         self.parts.push(term);
         self
     }
@@ -161,7 +269,7 @@ impl<Values, Types> PolynomialBuilder<Values, Types> {
 // For example given some expression (1 + x)(1 + y)
 // We can not determine can those be multiplyed until we do not finalize its types
 //
-// So we need to split polynomial state into two: 
+// So we need to split polynomial state into two:
 // 1. symbol-polynomial - do not require knowing type of each expression in it,
 // but because of that prohibits value-finalizing and simplification (which is not yet implemented)
 // 2. finalized-polynomial - requires knowing type of each expression in it and therefore can be value-finalized and simplified
@@ -176,4 +284,4 @@ impl<Values, Types> PolynomialBuilder<Values, Types> {
 //             builder: PolynomialBuilder::new().term_builder( )
 //         }
 //     }
-// } 
+// }
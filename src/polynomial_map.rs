@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{CompositionError, FinalizeError, PolynomialMapError},
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::{Symbol, SymbolsProvider},
+    traits::{PolyTypes, PolyValues},
+};
+
+/// Ordered list of polynomials over the symbols of one provider,
+/// for scalar domains it is a map `ℝⁿ → ℝᵐ` with one polynomial per output coordinate.
+#[derive(Clone)]
+pub struct PolynomialMap<Values, Types> {
+    symbols: SymbolsProvider<Types>,
+    components: Vec<Polynomial<Values, Types>>,
+}
+
+impl<Values, Types> PolynomialMap<Values, Types> {
+    pub fn symbols(&self) -> &SymbolsProvider<Types> {
+        &self.symbols
+    }
+
+    pub fn components(&self) -> &[Polynomial<Values, Types>] {
+        &self.components
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl<Values, Types> PolynomialMap<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Map with the given components, every symbol they use must be a symbol of `symbols`.
+    pub fn new(
+        symbols: &SymbolsProvider<Types>,
+        components: Vec<Polynomial<Values, Types>>,
+    ) -> Result<Self, PolynomialMapError> {
+        if let Some(label) = components
+            .iter()
+            .find_map(|component| foreign_symbol(symbols, component))
+        {
+            return Err(PolynomialMapError::ForeignSymbol(label));
+        }
+        Ok(PolynomialMap {
+            symbols: symbols.clone(),
+            components,
+        })
+    }
+
+    // For components that are known to use only symbols of `symbols`.
+    pub(crate) fn new_unchecked(
+        symbols: &SymbolsProvider<Types>,
+        components: Vec<Polynomial<Values, Types>>,
+    ) -> Self {
+        PolynomialMap {
+            symbols: symbols.clone(),
+            components,
+        }
+    }
+
+    /// Matrix of derivatives, row `i` holds derivatives of the `i`-th component
+    /// by every one of `variables` (see `Polynomial::derivative`).
+    pub fn jacobian(
+        &self,
+        variables: &[Symbol<Types>],
+    ) -> Result<Vec<Vec<Polynomial<Values, Types>>>, FinalizeError> {
+        self.components
+            .iter()
+            .map(|component| component.gradient(variables))
+            .collect()
+    }
+
+    /// Composition `self ∘ inner`: `variables[j]` is replaced by the `j`-th component of `inner`
+    /// in every component of `self`. All symbols are substituted simultaneously,
+    /// so components of `inner` may use `variables` themselves.
+    ///
+    /// Typed symbols can only be replaced by polynomials of the same type
+    /// (components that can not be typed are not checked).
+    pub fn compose(
+        &self,
+        variables: &[Symbol<Types>],
+        inner: &PolynomialMap<Values, Types>,
+    ) -> Result<Self, CompositionError> {
+        if variables.len() != inner.len() {
+            return Err(CompositionError::ArityMismatch(
+                variables.len(),
+                inner.len(),
+            ));
+        }
+        if let Some(label) = inner
+            .components
+            .iter()
+            .find_map(|component| foreign_symbol(&self.symbols, component))
+        {
+            return Err(CompositionError::ForeignSymbol(label));
+        }
+        let mut substitution = HashMap::new();
+        for (symbol, component) in variables.iter().zip(&inner.components) {
+            if let (Some(symbol_type), Ok(component_type)) =
+                (&symbol.associated_type, component.as_type())
+            {
+                if *symbol_type != component_type {
+                    return Err(CompositionError::TypeMismatch(symbol.label.clone()));
+                }
+            }
+            substitution.insert(symbol.clone(), component);
+        }
+        Ok(PolynomialMap {
+            symbols: self.symbols.clone(),
+            components: self
+                .components
+                .iter()
+                .map(|component| substitute(component.sub_poly(), &substitution))
+                .collect(),
+        })
+    }
+}
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Derivatives by every one of `variables` (see `Polynomial::derivative`).
+    pub fn gradient(&self, variables: &[Symbol<Types>]) -> Result<Vec<Self>, FinalizeError> {
+        variables
+            .iter()
+            .map(|variable| self.derivative(variable))
+            .collect()
+    }
+}
+
+// Label of a symbol of `poly` that is not the symbol with the same label in `symbols`.
+fn foreign_symbol<Values, Types>(
+    symbols: &SymbolsProvider<Types>,
+    poly: &Polynomial<Values, Types>,
+) -> Option<String> {
+    poly.free_symbols()
+        .into_iter()
+        .find(|symbol| symbols.get(&symbol.label).as_ref() != Some(symbol))
+        .map(|symbol| symbol.label.clone())
+}
+
+fn substitute<Values, Types>(
+    poly: &SubPoly<Values, Types>,
+    substitution: &HashMap<Symbol<Types>, &Polynomial<Values, Types>>,
+) -> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut builder = PolynomialBuilder::new();
+    for term in poly.terms() {
+        let mut term_builder = builder.term_builder(term.coefficient().clone());
+        for factor in term.factors() {
+            term_builder = match factor.factor() {
+                Factor::Value(value) => term_builder.value(value.value().clone(), factor.power()),
+                Factor::Variable(variable) => match substitution.get(variable.symbol()) {
+                    Some(&polynomial) => {
+                        term_builder.polynomial(polynomial.clone(), factor.power())
+                    }
+                    None => term_builder.variable(variable.symbol().clone(), factor.power()),
+                },
+                Factor::SubPoly(poly) => {
+                    term_builder.polynomial(substitute(poly, substitution), factor.power())
+                }
+            };
+        }
+        builder = term_builder.build();
+    }
+    builder
+        .build()
+        .expect("terms of an existing polynomial are always built")
+}
@@ -0,0 +1,228 @@
+use std::sync::OnceLock;
+
+use crate::{
+    error::FinalizeError,
+    traits::{PolyTypes, PolyValues, PowerStrategy},
+};
+
+// Shortest addition chains are searched only for powers up to this bound,
+// bigger powers fall back to binary powering.
+const ADDITION_CHAIN_LIMIT: u64 = 128;
+
+// The search is exponential, so each chain is searched once per process and then reused.
+static ADDITION_CHAINS: [OnceLock<Vec<(usize, usize)>>; ADDITION_CHAIN_LIMIT as usize] =
+    [const { OnceLock::new() }; ADDITION_CHAIN_LIMIT as usize];
+
+// Cached `shortest_addition_chain` for powers up to `ADDITION_CHAIN_LIMIT`.
+fn addition_chain(power: u64) -> &'static [(usize, usize)] {
+    assert!(power > 0 && power <= ADDITION_CHAIN_LIMIT);
+    ADDITION_CHAINS[power as usize - 1].get_or_init(|| shortest_addition_chain(power))
+}
+
+pub fn power_type<Types>(base: Types, power: u64) -> Result<Types, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+{
+    if power == 1 {
+        return Ok(base);
+    }
+    match base.power_strategy() {
+        PowerStrategy::Repeated | PowerStrategy::Custom if power > 1 => {
+            repeated_power_type(base, power)
+        }
+        _ => {
+            // zero power and squaring based strategies require type to be closed under multiplication
+            let mul_result = (base.clone() * base.clone())
+                .result
+                .ok_or(FinalizeError::NoTypeToFinalize)?;
+            if mul_result == base {
+                Ok(mul_result)
+            } else {
+                Err(FinalizeError::NoTypeToFinalize)
+            }
+        }
+    }
+}
+
+// Types of x, x^2, x^3, ... depend only on the previous one,
+// so as soon as some type repeats the rest of the sequence is periodic.
+fn repeated_power_type<Types>(base: Types, power: u64) -> Result<Types, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+{
+    let mut seen = vec![base.clone()];
+    while (seen.len() as u64) < power {
+        let next = (seen.last().unwrap().clone() * base.clone())
+            .result
+            .ok_or(FinalizeError::NoTypeToFinalize)?;
+        if let Some(cycle_start) = seen.iter().position(|seen_type| *seen_type == next) {
+            let cycle_len = seen.len() - cycle_start;
+            let index = cycle_start as u64 + (power - 1 - cycle_start as u64) % cycle_len as u64;
+            return Ok(seen[index as usize].clone());
+        }
+        seen.push(next);
+    }
+    Ok(seen.pop().unwrap())
+}
+
+pub fn power_value<Types, Values>(
+    base: Values,
+    power: u64,
+    strategy: PowerStrategy,
+) -> Result<Values, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    assert!(power > 0);
+    match strategy {
+        PowerStrategy::Repeated => repeated_power(base, power),
+        PowerStrategy::Binary => binary_power(base, power),
+        PowerStrategy::AdditionChain if power <= ADDITION_CHAIN_LIMIT => {
+            addition_chain_power(base, power)
+        }
+        PowerStrategy::AdditionChain => binary_power(base, power),
+        PowerStrategy::Custom => base.pow(power).ok_or(FinalizeError::NoValueToFinalize),
+    }
+}
+
+fn mul<Types, Values>(lhs: Values, rhs: Values) -> Result<Values, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    (lhs * rhs).ok_or(FinalizeError::NoValueToFinalize)
+}
+
+fn repeated_power<Types, Values>(base: Values, power: u64) -> Result<Values, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    (1..power).try_fold(base.clone(), |result, _| mul(result, base.clone()))
+}
+
+fn binary_power<Types, Values>(base: Values, power: u64) -> Result<Values, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut power = power;
+    let mut base = base;
+    while power & 1 == 0 {
+        base = mul(base.clone(), base)?;
+        power /= 2;
+    }
+    let mut result = base.clone();
+    while power > 1 {
+        power /= 2;
+        base = mul(base.clone(), base)?;
+        if power & 1 == 1 {
+            result = mul(result, base.clone())?;
+        }
+    }
+    Ok(result)
+}
+
+fn addition_chain_power<Types, Values>(base: Values, power: u64) -> Result<Values, FinalizeError>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    let mut values = vec![base];
+    for &(i, j) in addition_chain(power) {
+        let next = mul(values[i].clone(), values[j].clone())?;
+        values.push(next);
+    }
+    Ok(values.pop().unwrap())
+}
+
+// Multiplications done by `power_value` for given strategy in the same format as `shortest_addition_chain`.
+// `Custom` powers are typed as repeated ones, so they are expanded the same way.
+pub fn power_chain(power: u64, strategy: PowerStrategy) -> Vec<(usize, usize)> {
+    assert!(power > 0);
+    match strategy {
+        PowerStrategy::Repeated | PowerStrategy::Custom => (0..power as usize - 1)
+            .map(|previous| (previous, 0))
+            .collect(),
+        PowerStrategy::AdditionChain if power <= ADDITION_CHAIN_LIMIT => {
+            addition_chain(power).to_vec()
+        }
+        PowerStrategy::Binary | PowerStrategy::AdditionChain => binary_chain(power),
+    }
+}
+
+// Mirrors `binary_power`, last element of the chain is the result.
+fn binary_chain(power: u64) -> Vec<(usize, usize)> {
+    let mut steps = vec![];
+    let mut power = power;
+    let mut base = 0;
+    while power & 1 == 0 {
+        steps.push((base, base));
+        base = steps.len();
+        power /= 2;
+    }
+    let mut result = base;
+    while power > 1 {
+        power /= 2;
+        steps.push((base, base));
+        base = steps.len();
+        if power & 1 == 1 {
+            steps.push((result, base));
+            result = steps.len();
+        }
+    }
+    steps
+}
+
+// Returns steps of the shortest addition chain for `power`:
+// k-th step says that (k + 1)-th element of the chain is the sum of elements with given indices
+// (0-th element is always 1).
+fn shortest_addition_chain(power: u64) -> Vec<(usize, usize)> {
+    assert!(power > 0);
+    let mut chain = vec![1];
+    let mut steps = vec![];
+    let mut depth = 0;
+    while !search_addition_chain(power, depth, &mut chain, &mut steps) {
+        depth += 1;
+    }
+    steps
+}
+
+// Iterative deepening search over ascending chains.
+fn search_addition_chain(
+    power: u64,
+    depth: u32,
+    chain: &mut Vec<u64>,
+    steps: &mut Vec<(usize, usize)>,
+) -> bool {
+    let last = *chain.last().unwrap();
+    if last == power {
+        return true;
+    }
+    // every step can at most double the last element
+    if depth == 0 || last.checked_shl(depth).is_some_and(|bound| bound < power) {
+        return false;
+    }
+    let mut tried = vec![];
+    for i in (0..chain.len()).rev() {
+        for j in (0..=i).rev() {
+            let next = chain[i] + chain[j];
+            if next <= last {
+                break;
+            }
+            if next > power || tried.contains(&next) {
+                continue;
+            }
+            tried.push(next);
+            chain.push(next);
+            steps.push((i, j));
+            if search_addition_chain(power, depth - 1, chain, steps) {
+                return true;
+            }
+            chain.pop();
+            steps.pop();
+        }
+    }
+    false
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    factor::{Factor, SubPoly, Value, Variable},
+    polynomial::Polynomial,
+    symbol::Symbol,
+    term::{MonomialFactor, Term},
+    visit::PolyVisitor,
+};
+
+impl<Values, Types> Polynomial<Values, Types> {
+    /// Symbols that are still present anywhere in the tree, ordered by id.
+    /// Symbols that appear only with zero powers (like `x^0`) are included as well,
+    /// since they still take part in typing the polynomial.
+    pub fn free_symbols(&self) -> BTreeSet<Symbol<Types>> {
+        let mut collector = SymbolCollector {
+            symbols: BTreeSet::new(),
+        };
+        self.visit(&mut collector);
+        collector.symbols
+    }
+
+    pub fn contains(&self, symbol: &Symbol<Types>) -> bool {
+        self.free_symbols().contains(symbol)
+    }
+
+    /// Highest power of `symbol` among the terms, powers of sub-polynomials multiply degrees of their contents.
+    /// It is an upper bound: terms that would cancel each other after expansion are still counted.
+    /// Degrees that do not fit into `u64` saturate at `u64::MAX`.
+    pub fn degree_in(&self, symbol: &Symbol<Types>) -> u64 {
+        degree(self.sub_poly(), &|variable| variable.symbol() == symbol)
+    }
+
+    /// Highest sum of powers of all symbols among the terms, computed the same way as `degree_in`.
+    pub fn total_degree(&self) -> u64 {
+        degree(self.sub_poly(), &|_| true)
+    }
+
+    /// Number of top level terms.
+    pub fn term_count(&self) -> usize {
+        self.terms().len()
+    }
+
+    /// Number of nested sub-polynomials on the deepest path, the polynomial itself counts as one.
+    pub fn depth(&self) -> usize {
+        let mut counter = NodeCounter::default();
+        self.visit(&mut counter);
+        counter.max_depth
+    }
+
+    /// Number of nodes in the tree: sub-polynomials, terms, monomial factors, values and variables
+    /// (the same nodes `to_dot` draws).
+    pub fn node_count(&self) -> usize {
+        let mut counter = NodeCounter::default();
+        self.visit(&mut counter);
+        counter.nodes
+    }
+}
+
+pub(crate) fn degree<Values, Types, Counted>(
+    poly: &SubPoly<Values, Types>,
+    counted: &Counted,
+) -> u64
+where
+    Counted: Fn(&Variable<Types>) -> bool,
+{
+    poly.terms()
+        .iter()
+        .map(|term| {
+            term.factors()
+                .iter()
+                .map(|factor| match factor.factor() {
+                    Factor::Value(_) => 0,
+                    Factor::Variable(variable) if counted(variable) => factor.power(),
+                    Factor::Variable(_) => 0,
+                    Factor::SubPoly(poly) => factor.power().saturating_mul(degree(poly, counted)),
+                })
+                .fold(0, u64::saturating_add)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+struct SymbolCollector<Types> {
+    symbols: BTreeSet<Symbol<Types>>,
+}
+
+impl<Values, Types> PolyVisitor<Values, Types> for SymbolCollector<Types> {
+    fn pre_variable(&mut self, variable: &Variable<Types>) {
+        self.symbols.insert(variable.symbol().clone());
+    }
+}
+
+#[derive(Default)]
+struct NodeCounter {
+    nodes: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<Values, Types> PolyVisitor<Values, Types> for NodeCounter {
+    fn pre_sub_poly(&mut self, _: &SubPoly<Values, Types>) {
+        self.nodes += 1;
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn post_sub_poly(&mut self, _: &SubPoly<Values, Types>) {
+        self.depth -= 1;
+    }
+
+    fn pre_term(&mut self, _: &Term<Values, Types>) {
+        self.nodes += 1;
+    }
+
+    fn pre_monomial_factor(&mut self, _: &MonomialFactor<Values, Types>) {
+        self.nodes += 1;
+    }
+
+    fn pre_value(&mut self, _: &Value<Values, Types>) {
+        self.nodes += 1;
+    }
+
+    fn pre_variable(&mut self, _: &Variable<Types>) {
+        self.nodes += 1;
+    }
+}
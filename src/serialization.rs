@@ -0,0 +1,245 @@
+use std::marker::PhantomData;
+
+use serde::{
+    de::{self, DeserializeSeed},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::{Symbol, SymbolInfo, SymbolsProvider},
+    traits::{PolyTypes, PolyValues},
+};
+
+// A polynomial is stored together with declarations of every symbol it uses (ordered as symbols are)
+// and variables refer to those declarations by their index in the document,
+// so symbols of different providers can be written together.
+// On deserialization symbols are linked by label, so ids of the read symbols can differ from the written ones.
+#[derive(Serialize, Deserialize)]
+struct SerializedPolynomial<Values, Types> {
+    symbols: Vec<SymbolDeclaration<Types>>,
+    terms: Vec<SerializedTerm<Values>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SymbolDeclaration<Types> {
+    label: String,
+    associated_type: Option<Types>,
+    latex: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTerm<Values> {
+    coefficient: Values,
+    factors: Vec<(SerializedFactor<Values>, u64)>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum SerializedFactor<Values> {
+    Value(Values),
+    Variable(usize),
+    SubPoly(Vec<SerializedTerm<Values>>),
+}
+
+struct Writer<Types> {
+    // sorted, index in this list is the index of the declaration
+    symbols: Vec<Symbol<Types>>,
+}
+
+impl<Types> Writer<Types>
+where
+    Types: Clone,
+{
+    fn sub_poly<Values>(&self, poly: &SubPoly<Values, Types>) -> Vec<SerializedTerm<Values>>
+    where
+        Values: Clone,
+    {
+        poly.terms()
+            .iter()
+            .map(|term| SerializedTerm {
+                coefficient: term.coefficient().clone(),
+                factors: term
+                    .factors()
+                    .iter()
+                    .map(|factor| (self.factor(factor.factor()), factor.power()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn factor<Values>(&self, factor: &Factor<Values, Types>) -> SerializedFactor<Values>
+    where
+        Values: Clone,
+    {
+        match factor {
+            Factor::Value(value) => SerializedFactor::Value(value.value().clone()),
+            Factor::Variable(variable) => {
+                SerializedFactor::Variable(self.symbol(variable.symbol()))
+            }
+            Factor::SubPoly(poly) => SerializedFactor::SubPoly(self.sub_poly(poly)),
+        }
+    }
+
+    fn symbol(&self, symbol: &Symbol<Types>) -> usize {
+        self.symbols
+            .binary_search(symbol)
+            .expect("symbols of the polynomial are collected")
+    }
+
+    fn declarations(self) -> Vec<SymbolDeclaration<Types>> {
+        self.symbols
+            .into_iter()
+            .map(|symbol| SymbolDeclaration {
+                label: symbol.label.clone(),
+                associated_type: symbol.associated_type.clone(),
+                latex: symbol.latex.clone(),
+            })
+            .collect()
+    }
+}
+
+impl<Values, Types> Serialize for Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types> + Serialize,
+    Values: PolyValues<Types, Values> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let writer = Writer {
+            symbols: self.free_symbols().into_iter().collect(),
+        };
+        let terms = writer.sub_poly(self.sub_poly());
+        SerializedPolynomial {
+            symbols: writer.declarations(),
+            terms,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a polynomial and links its symbols to the ones of `symbols` with the same labels.
+/// Linked symbols must have the same associated type as the serialized declaration,
+/// and symbols that are not in the provider are reported as errors unless `declare_missing` is set.
+pub struct PolynomialSeed<'a, Values, Types> {
+    symbols: &'a SymbolsProvider<Types>,
+    declare_missing: bool,
+    values: PhantomData<Values>,
+}
+
+impl<'a, Values, Types> PolynomialSeed<'a, Values, Types> {
+    pub fn new(symbols: &'a SymbolsProvider<Types>) -> Self {
+        PolynomialSeed {
+            symbols,
+            declare_missing: false,
+            values: PhantomData,
+        }
+    }
+
+    /// Adds symbols that are not in the provider yet from their serialized declarations.
+    pub fn declare_missing(mut self) -> Self {
+        self.declare_missing = true;
+        self
+    }
+}
+
+impl<'de, Values, Types> DeserializeSeed<'de> for PolynomialSeed<'_, Values, Types>
+where
+    Types: PolyTypes<Types> + Deserialize<'de>,
+    Values: PolyValues<Types, Values> + Deserialize<'de>,
+{
+    type Value = Polynomial<Values, Types>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let serialized = SerializedPolynomial::<Values, Types>::deserialize(deserializer)?;
+        let symbols = serialized
+            .symbols
+            .into_iter()
+            .map(|declaration| link_symbol(self.symbols, declaration, self.declare_missing))
+            .collect::<Result<Vec<_>, _>>()?;
+        read_terms(&symbols, serialized.terms)
+    }
+}
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    /// Shorthand for deserializing with `PolynomialSeed` (without `declare_missing`).
+    pub fn deserialize_into<'de, D>(
+        deserializer: D,
+        symbols: &SymbolsProvider<Types>,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        Types: Deserialize<'de>,
+        Values: Deserialize<'de>,
+    {
+        PolynomialSeed::new(symbols).deserialize(deserializer)
+    }
+}
+
+fn link_symbol<Types, E>(
+    symbols: &SymbolsProvider<Types>,
+    declaration: SymbolDeclaration<Types>,
+    declare_missing: bool,
+) -> Result<Symbol<Types>, E>
+where
+    Types: PolyTypes<Types>,
+    E: de::Error,
+{
+    match symbols.get(&declaration.label) {
+        Some(symbol) if symbol.associated_type != declaration.associated_type => {
+            Err(E::custom(format_args!(
+                "symbol `{}` is already declared with a different type",
+                declaration.label
+            )))
+        }
+        Some(symbol) => Ok(symbol),
+        None if !declare_missing => Err(E::custom(format_args!(
+            "symbol `{}` is not declared",
+            declaration.label
+        ))),
+        None => Ok(symbols.add(SymbolInfo {
+            label: declaration.label,
+            associated_type: declaration.associated_type,
+            latex: declaration.latex,
+        })),
+    }
+}
+
+fn read_terms<Values, Types, E>(
+    symbols: &[Symbol<Types>],
+    terms: Vec<SerializedTerm<Values>>,
+) -> Result<Polynomial<Values, Types>, E>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+    E: de::Error,
+{
+    let mut builder = PolynomialBuilder::new();
+    for term in terms {
+        let mut term_builder = builder.term_builder(term.coefficient);
+        for (factor, power) in term.factors {
+            term_builder = match factor {
+                SerializedFactor::Value(value) => term_builder.value(value, power),
+                SerializedFactor::Variable(index) => {
+                    let symbol = symbols.get(index).ok_or_else(|| {
+                        E::custom(format_args!(
+                            "variable refers to undeclared symbol {}",
+                            index
+                        ))
+                    })?;
+                    term_builder.variable(symbol.clone(), power)
+                }
+                SerializedFactor::SubPoly(terms) => {
+                    term_builder.polynomial(read_terms(symbols, terms)?, power)
+                }
+            };
+        }
+        builder = term_builder.build();
+    }
+    builder
+        .build()
+        .map_err(|error| E::custom(format_args!("{:?}", error)))
+}
@@ -1,21 +1,31 @@
 use std::{
-    cell::RefCell,
+    cmp::Ordering,
     collections::HashMap,
     fmt::{self, Debug, Display},
     hash::Hash,
     ops::Deref,
-    rc::Rc,
+    sync::atomic::{self, AtomicUsize},
 };
 
+use crate::sync::{Lock, Shared};
+
+// Number of providers created so far, used to order symbols of different providers
+// the same way on every run that creates providers in the same order.
+static PROVIDERS: AtomicUsize = AtomicUsize::new(0);
+
 #[derive(Clone)]
 pub struct SymbolsProvider<Types> {
-    symbols: Rc<RefCell<SymbolsProviderData<Types>>>,
+    symbols: Shared<Lock<SymbolsProviderData<Types>>>,
+    serial: usize,
 }
 
+// LaTeX is set through `with_latex`, so the info can gain more optional fields
+// without changing how it is constructed.
 #[derive(Clone)]
 pub struct SymbolInfo<Types> {
     pub label: String,
     pub associated_type: Option<Types>,
+    pub(crate) latex: Option<String>,
 }
 
 impl<Types> SymbolInfo<Types> {
@@ -23,16 +33,53 @@ impl<Types> SymbolInfo<Types> {
         SymbolInfo {
             label: label.to_owned(),
             associated_type,
+            latex: None,
         }
     }
 
     pub fn new_typed(label: &str, associated_type: Types) -> Self {
         Self::new(label, Some(associated_type))
     }
+
+    /// Sets LaTeX that is used for the symbol instead of its label (for example `\mathbf{v}`).
+    pub fn with_latex(mut self, latex: &str) -> Self {
+        self.latex = Some(latex.to_owned());
+        self
+    }
+
+    /// LaTeX set by `with_latex`, if any.
+    pub fn latex(&self) -> Option<&str> {
+        self.latex.as_deref()
+    }
+
+    pub fn to_latex(&self) -> String {
+        self.latex.clone().unwrap_or_else(|| self.label.clone())
+    }
+}
+
+/// Index of a symbol in the order it was added to its `SymbolsProvider`.
+/// It does not depend on memory layout, so it is the same on every run that declares symbols in the same order.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolId(usize);
+
+impl SymbolId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl Display for SymbolId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
 }
 
 pub struct Symbol<Types> {
-    info: Rc<SymbolInfo<Types>>,
+    id: SymbolId,
+    // serial number of the provider that created the symbol
+    provider: usize,
+    info: Shared<SymbolInfo<Types>>,
 }
 
 impl<Types> Debug for Symbol<Types>
@@ -60,16 +107,24 @@ where
 }
 
 impl<Types> Symbol<Types> {
-    fn new(info: SymbolInfo<Types>) -> Symbol<Types> {
+    fn new(id: SymbolId, provider: usize, info: SymbolInfo<Types>) -> Symbol<Types> {
         Symbol {
-            info: Rc::new(info),
+            id,
+            provider,
+            info: Shared::new(info),
         }
     }
+
+    pub fn id(&self) -> SymbolId {
+        self.id
+    }
 }
 
 impl<Types> Clone for Symbol<Types> {
     fn clone(&self) -> Self {
         Self {
+            id: self.id,
+            provider: self.provider,
             info: self.info.clone(),
         }
     }
@@ -77,13 +132,33 @@ impl<Types> Clone for Symbol<Types> {
 
 impl<Types> PartialEq for Symbol<Types> {
     fn eq(&self, other: &Self) -> bool {
-        Rc::ptr_eq(&self.info, &other.info)
+        Shared::ptr_eq(&self.info, &other.info)
+    }
+}
+
+impl<Types> Eq for Symbol<Types> {}
+
+// Ids are unique only inside one provider, so symbols of different providers with the same id
+// are ordered by label and then by the order their providers were created in
+// (never by address, so the order is the same on every run).
+impl<Types> Ord for Symbol<Types> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id
+            .cmp(&other.id)
+            .then_with(|| self.label.cmp(&other.label))
+            .then_with(|| self.provider.cmp(&other.provider))
+    }
+}
+
+impl<Types> PartialOrd for Symbol<Types> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 impl<Types> Hash for Symbol<Types> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        Rc::as_ptr(&self.info).hash(state);
+        self.id.hash(state);
     }
 }
 
@@ -106,19 +181,29 @@ type SymbolsProviderData<Types> = HashMap<String, Symbol<Types>>;
 impl<Types> SymbolsProvider<Types> {
     pub fn empty() -> Self {
         SymbolsProvider {
-            symbols: Rc::new(RefCell::new(HashMap::new())),
+            symbols: Shared::new(Lock::new(HashMap::new())),
+            serial: PROVIDERS.fetch_add(1, atomic::Ordering::Relaxed),
         }
     }
 
     pub fn add(&self, info: SymbolInfo<Types>) -> Symbol<Types> {
-        let mut symbols = self.symbols.borrow_mut();
+        let mut symbols = self.symbols.write();
+        // symbols are never removed, so the number of already added ones is a fresh id
+        let id = SymbolId(symbols.len());
         symbols
             .entry(info.label.clone())
-            .or_insert(Symbol::new(info))
+            .or_insert_with(|| Symbol::new(id, self.serial, info))
             .clone()
     }
 
     pub fn get(&self, label: &str) -> Option<Symbol<Types>> {
-        self.symbols.borrow().get(label).cloned()
+        self.symbols.read().get(label).cloned()
+    }
+
+    /// All added symbols ordered by their ids.
+    pub fn symbols(&self) -> Vec<Symbol<Types>> {
+        let mut symbols = self.symbols.read().values().cloned().collect::<Vec<_>>();
+        symbols.sort();
+        symbols
     }
 }
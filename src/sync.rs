@@ -0,0 +1,95 @@
+// Everything that changes with the `parallel` feature lives here,
+// so the rest of the crate does not have to know which backend is used.
+
+#[cfg(not(feature = "parallel"))]
+mod backend {
+    use std::cell::{Ref, RefCell, RefMut};
+
+    pub type Shared<T> = std::rc::Rc<T>;
+
+    pub type OnceCell<T> = std::cell::OnceCell<T>;
+
+    pub struct Lock<T>(RefCell<T>);
+
+    impl<T> Lock<T> {
+        pub fn new(value: T) -> Self {
+            Lock(RefCell::new(value))
+        }
+
+        pub fn read(&self) -> Ref<'_, T> {
+            self.0.borrow()
+        }
+
+        pub fn write(&self) -> RefMut<'_, T> {
+            self.0.borrow_mut()
+        }
+    }
+
+    // items are mapped lazily, so the first error stops the rest from being computed
+    pub fn try_fold_ordered<T, R, E, F, G>(items: Vec<T>, f: F, fold: G) -> Result<Option<R>, E>
+    where
+        F: Fn(T) -> Result<R, E>,
+        G: FnMut(R, R) -> Result<R, E>,
+    {
+        super::fold_results(items.into_iter().map(f), fold)
+    }
+}
+
+#[cfg(feature = "parallel")]
+mod backend {
+    use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    use rayon::prelude::*;
+
+    pub type Shared<T> = std::sync::Arc<T>;
+
+    pub type OnceCell<T> = std::sync::OnceLock<T>;
+
+    pub struct Lock<T>(RwLock<T>);
+
+    impl<T> Lock<T> {
+        pub fn new(value: T) -> Self {
+            Lock(RwLock::new(value))
+        }
+
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().expect("symbols lock is poisoned")
+        }
+
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().expect("symbols lock is poisoned")
+        }
+    }
+
+    // items are mapped concurrently, but rayon keeps their order on collect,
+    // so they are still folded left to right (sums stay correct for non-commutative addition)
+    pub fn try_fold_ordered<T, R, E, F, G>(items: Vec<T>, f: F, fold: G) -> Result<Option<R>, E>
+    where
+        T: Send,
+        R: Send,
+        E: Send,
+        F: Fn(T) -> Result<R, E> + Send + Sync,
+        G: FnMut(R, R) -> Result<R, E>,
+    {
+        let results = items.into_par_iter().map(f).collect::<Vec<_>>();
+        super::fold_results(results.into_iter(), fold)
+    }
+}
+
+pub use backend::*;
+
+// Folds results left to right, `None` if there are no items.
+fn fold_results<R, E, G>(
+    mut results: impl Iterator<Item = Result<R, E>>,
+    mut fold: G,
+) -> Result<Option<R>, E>
+where
+    G: FnMut(R, R) -> Result<R, E>,
+{
+    let Some(first) = results.next() else {
+        return Ok(None);
+    };
+    results
+        .try_fold(first?, |accumulated, item| fold(accumulated, item?))
+        .map(Some)
+}
@@ -1,10 +1,15 @@
-use std::{fmt::{self, Debug, Display}, ops::Neg};
+use std::{
+    fmt::{self, Debug, Display},
+    ops::Neg,
+};
 
 use crate::{
     error::{BuilderError, FinalizeError},
-    factor::{Factor, Finalizable, Substitutiable, Value, Variable},
+    factor::{Factor, Finalizable, Foldable, Substitutiable, ToLatex, TypeCache, Value, Variable},
     polynomial::{Polynomial, PolynomialBuilder},
+    power::{power_type, power_value},
     symbol::Symbol,
+    sync::try_fold_ordered,
     traits::{PolyTypes, PolyValues},
 };
 
@@ -12,6 +17,29 @@ use crate::{
 pub struct Term<Values, Types> {
     coefficient: Values,
     monomial: Vec<MonomialFactor<Values, Types>>,
+    finalized_type: TypeCache<Types>,
+}
+
+impl<Values, Types> Term<Values, Types> {
+    fn new(coefficient: Values, monomial: Vec<MonomialFactor<Values, Types>>) -> Self {
+        Term {
+            coefficient,
+            monomial,
+            finalized_type: TypeCache::new(),
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.monomial.is_empty()
+    }
+
+    pub fn coefficient(&self) -> &Values {
+        &self.coefficient
+    }
+
+    pub fn factors(&self) -> &[MonomialFactor<Values, Types>] {
+        &self.monomial
+    }
 }
 
 // Seems like finalize of type and value are vere similar operations,
@@ -26,14 +54,13 @@ where
     type Output = Term<Values, Types>;
 
     fn substitute(self, to: Symbol<Types>, factor: Factor<Values, Types>) -> Self::Output {
-        Term {
-            coefficient: self.coefficient,
-            monomial: self
-                .monomial
+        Term::new(
+            self.coefficient,
+            self.monomial
                 .into_iter()
                 .map(|monomial_factor| monomial_factor.substitute(to.clone(), factor.clone()))
                 .collect(),
-        }
+        )
     }
 }
 
@@ -43,6 +70,38 @@ where
     Values: PolyValues<Types, Values>,
 {
     fn finalize_type(&self) -> Result<Types, FinalizeError> {
+        self.finalized_type
+            .get_or_init(|| self.infer_type())
+            .clone()
+    }
+
+    fn finalize_value(self) -> Result<Values, FinalizeError> {
+        let finalized_type = self.finalize_type()?;
+        // factors are independent from each other, so they may be evaluated concurrently
+        let monomial_value = try_fold_ordered(
+            self.monomial,
+            |factor| factor.finalize_value(),
+            |product, factor_value| {
+                (product * factor_value).ok_or(FinalizeError::NoValueToFinalize)
+            },
+        )?;
+        let result = match monomial_value {
+            Some(monomial_value) => {
+                (self.coefficient * monomial_value).expect("type verified operation failed")
+            }
+            None => self.coefficient,
+        };
+        assert!(result.as_type() == finalized_type);
+        Ok(result)
+    }
+}
+
+impl<Values, Types> Term<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn infer_type(&self) -> Result<Types, FinalizeError> {
         let coef_type = self.coefficient.as_type();
         let monomial_type =
             self.monomial
@@ -63,27 +122,39 @@ where
             None => Ok(coef_type),
         }
     }
+}
 
-    fn finalize_value(self) -> Result<Values, FinalizeError> {
-        let finalized_type = self.finalize_type()?;
-        let monomial_value =
-            self.monomial
-                .into_iter()
-                .try_fold(None, |pref: Option<Values>, factor| {
-                    let factor_value = factor.finalize_value()?;
-                    Ok(Some(match pref {
-                        None => factor_value,
-                        Some(pref_value) => (pref_value * factor_value).ok_or(FinalizeError::NoValueToFinalize)?,
-                    }))
-                })?;
-        let result = match monomial_value {
-            Some(monomial_value) => {
-                (self.coefficient * monomial_value).expect("type verified operation failed")
-            }
-            None => self.coefficient.into(),
-        };
-        assert!(result.as_type() == finalized_type);
-        Ok(result)
+impl<Values, Types> Foldable for Term<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    type Output = Self;
+
+    fn fold_constants(self) -> Self::Output {
+        // multiplication is associative, so adjacent constant factors can be merged into one
+        let monomial = self
+            .monomial
+            .into_iter()
+            .map(|monomial_factor| monomial_factor.fold_constants())
+            .fold(
+                Vec::new(),
+                |mut monomial: Vec<MonomialFactor<_, _>>, factor| {
+                    match monomial.last().and_then(|last| last.merge_value(&factor)) {
+                        Some(merged) => *monomial.last_mut().unwrap() = merged,
+                        None => monomial.push(factor),
+                    }
+                    monomial
+                },
+            );
+        let folded = Term::new(self.coefficient, monomial);
+        if folded.is_constant() || !folded.monomial.iter().all(|factor| factor.is_value()) {
+            return folded;
+        }
+        match folded.clone().finalize_value() {
+            Ok(coefficient) => Term::new(coefficient, vec![]),
+            Err(_) => folded,
+        }
     }
 }
 
@@ -94,9 +165,39 @@ where
 {
     type Output = Self;
 
-    fn neg(mut self) -> Self::Output {
-        self.coefficient = -self.coefficient;
-        self
+    fn neg(self) -> Self::Output {
+        Term::new(-self.coefficient, self.monomial)
+    }
+}
+
+impl<Values, Types> ToLatex for Term<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn to_latex(&self) -> String {
+        // (latex, is it a value) for the coefficient and every factor
+        let coefficient = (!self.coefficient.is_one() || self.monomial.is_empty())
+            .then(|| (self.coefficient.to_latex(), true));
+        let factors = self.monomial.iter().map(|monomial_factor| {
+            (
+                monomial_factor.to_latex(),
+                matches!(monomial_factor.factor, Factor::Value(_)),
+            )
+        });
+        let mut result = String::new();
+        let mut previous_is_value = None;
+        for (latex, is_value) in coefficient.into_iter().chain(factors) {
+            match previous_is_value {
+                // two values next to each other would look like one number
+                Some(true) if is_value => result.push_str(" \\cdot "),
+                Some(_) => result.push(' '),
+                None => {}
+            }
+            result.push_str(&latex);
+            previous_is_value = Some(is_value);
+        }
+        result
     }
 }
 
@@ -140,9 +241,50 @@ where
 }
 
 #[derive(Clone)]
-struct MonomialFactor<Values, Types> {
+pub struct MonomialFactor<Values, Types> {
     factor: Factor<Values, Types>,
     power: u64,
+    finalized_type: TypeCache<Types>,
+}
+
+impl<Values, Types> MonomialFactor<Values, Types> {
+    fn new(factor: Factor<Values, Types>, power: u64) -> Self {
+        MonomialFactor {
+            factor,
+            power,
+            finalized_type: TypeCache::new(),
+        }
+    }
+
+    fn is_value(&self) -> bool {
+        self.power == 1 && matches!(self.factor, Factor::Value(_))
+    }
+
+    pub fn factor(&self) -> &Factor<Values, Types> {
+        &self.factor
+    }
+
+    pub fn power(&self) -> u64 {
+        self.power
+    }
+}
+
+impl<Values, Types> MonomialFactor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn merge_value(&self, rhs: &Self) -> Option<Self> {
+        match (&self.factor, &rhs.factor) {
+            (Factor::Value(lhs_value), Factor::Value(rhs_value))
+                if self.power == 1 && rhs.power == 1 =>
+            {
+                (lhs_value.value().clone() * rhs_value.value().clone())
+                    .map(|value| MonomialFactor::new(Factor::Value(Value::new(value)), 1))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl<Values, Types> Substitutiable<Values, Types> for MonomialFactor<Values, Types>
@@ -153,10 +295,7 @@ where
     type Output = MonomialFactor<Values, Types>;
 
     fn substitute(self, to: Symbol<Types>, factor: Factor<Values, Types>) -> Self::Output {
-        MonomialFactor {
-            factor: self.factor.substitute(to.clone(), factor.clone()),
-            power: self.power,
-        }
+        MonomialFactor::new(self.factor.substitute(to, factor), self.power)
     }
 }
 
@@ -166,19 +305,9 @@ where
     Values: PolyValues<Types, Values>,
 {
     fn finalize_type(&self) -> Result<Types, FinalizeError> {
-        let factor_type = self.factor.finalize_type();
-        if self.power == 1 {
-            return factor_type;
-        }
-        let factor_type = factor_type?;
-        let mul_result = (factor_type.clone() * factor_type.clone())
-            .result
-            .ok_or(FinalizeError::NoTypeToFinalize)?;
-        if mul_result == factor_type {
-            Ok(mul_result)
-        } else {
-            Err(FinalizeError::NoTypeToFinalize)
-        }
+        self.finalized_type
+            .get_or_init(|| self.infer_type())
+            .clone()
     }
 
     fn finalize_value(self) -> Result<Values, FinalizeError> {
@@ -192,30 +321,55 @@ where
         if self.power == 0 {
             return Values::one_with_type(finalized_type).ok_or(FinalizeError::NoValueToFinalize);
         }
-        let mut factor_value = factor_value?;
-
-        // move this to separate place
-        let mut power = self.power;
-        while power & 2 == 0 {
-            factor_value =
-                (factor_value.clone() * factor_value).expect("type verified operation failed");
-            power /= 2;
+        let factor_value = factor_value?;
+        let strategy = factor_value.as_type().power_strategy();
+        let result = power_value(factor_value, self.power, strategy)?;
+        assert!(result.as_type() == finalized_type);
+        Ok(result)
+    }
+}
+
+impl<Values, Types> MonomialFactor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn infer_type(&self) -> Result<Types, FinalizeError> {
+        power_type(self.factor.finalize_type()?, self.power)
+    }
+}
+
+impl<Values, Types> Foldable for MonomialFactor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    type Output = Self;
+
+    fn fold_constants(self) -> Self::Output {
+        let folded = MonomialFactor::new(self.factor.fold_constants(), self.power);
+        if folded.power == 1 || !matches!(folded.factor, Factor::Value(_)) {
+            return folded;
         }
-        if power == 1 {
-            assert!(factor_value.as_type() == finalized_type);
-            return Ok(factor_value);
+        match folded.clone().finalize_value() {
+            Ok(value) => MonomialFactor::new(Factor::Value(Value::new(value)), 1),
+            Err(_) => folded,
         }
-        let mut result = factor_value.clone();
-        while power > 1 {
-            power /= 2;
-            factor_value =
-                (factor_value.clone() * factor_value).expect("type verified operation failed");
-            if power & 2 == 1 {
-                result = (result * factor_value.clone()).expect("type verified operation failed");
-            }
+    }
+}
+
+impl<Values, Types> ToLatex for MonomialFactor<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values>,
+{
+    fn to_latex(&self) -> String {
+        let factor = self.factor.to_latex();
+        match (&self.factor, self.power) {
+            (_, 1) => factor,
+            (Factor::Value(_), power) => format!("{{{}}}^{{{}}}", factor, power),
+            (_, power) => format!("{}^{{{}}}", factor, power),
         }
-        assert!(factor_value.as_type() == finalized_type);
-        Ok(factor_value)
     }
 }
 
@@ -252,10 +406,12 @@ where
         Ok(())
     }
 }
+type MaybeFactor<Values, Types> = Result<Factor<Values, Types>, BuilderError>;
+
 pub struct TermBuilder<Values, Types> {
     parent: PolynomialBuilder<Values, Types>,
     coefficient: Values,
-    factors: Vec<(Result<Factor<Values, Types>, BuilderError>, u64)>,
+    factors: Vec<(MaybeFactor<Values, Types>, u64)>,
 }
 
 impl<Values, Types> TermBuilder<Values, Types> {
@@ -314,13 +470,10 @@ impl<Values, Types> TermBuilder<Values, Types> {
             self.factors
                 .into_iter()
                 .map(|(maybe_factor, power)| {
-                    maybe_factor.map(|factor| MonomialFactor { factor, power })
+                    maybe_factor.map(|factor| MonomialFactor::new(factor, power))
                 })
                 .collect::<Result<Vec<_>, BuilderError>>()
-                .map(|monomial| Term {
-                    coefficient: self.coefficient,
-                    monomial,
-                }),
+                .map(|monomial| Term::new(self.coefficient, monomial)),
         )
     }
 }
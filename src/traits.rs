@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     fmt::{Debug, Display},
     ops::{Add, Mul, Neg},
 };
@@ -11,24 +12,157 @@ pub trait One {
     fn one() -> Self;
 }
 
+/// Bound that is required from `Types` and `Values` so polynomials can be evaluated concurrently.
+/// It is `Send + Sync` with the `parallel` feature and is implemented for everything otherwise.
+#[cfg(feature = "parallel")]
+pub trait ThreadSafe: Send + Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync> ThreadSafe for T {}
+
+#[cfg(not(feature = "parallel"))]
+pub trait ThreadSafe {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> ThreadSafe for T {}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct MulTraits<Types> {
     pub result: Option<Types>,
     pub commutative: bool,
 }
 
+/// How powers greater than one of some type are computed.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PowerStrategy {
+    /// `x * x * ... * x`. Requires only associativity,
+    /// so `x * x` is not required to have the same type as `x`.
+    Repeated,
+    /// Square-and-multiply. Requires `x * x` to have the same type as `x`.
+    Binary,
+    /// Shortest addition chain for small powers and square-and-multiply for the rest.
+    /// Has the same requirements as `Binary`, but does fewer multiplications.
+    AdditionChain,
+    /// Uses `PolyValues::pow`. Typed as `Repeated`.
+    Custom,
+}
+
+/// Computer algebra systems polynomials can be exported to (see `Polynomial::to_sympy` and others).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CasSyntax {
+    SymPy,
+    Maxima,
+    Mathematica,
+}
+
+/// Order of monomials (see `Polynomial::sort_terms` and `PolynomialMap::groebner_basis`).
+///
+/// Commutative monomials are given by exponents of the same symbols, where the first symbol is the greatest.
+/// Non-commutative monomials are words over the same symbols, letters are their indices, so smaller ones are greater.
+/// Words are run-length encoded: `(letter, power)` stands for `power` copies of the letter,
+/// powers are never zero but adjacent runs may have the same letter.
+pub trait MonomialOrder {
+    fn compare(&self, lhs: &[u64], rhs: &[u64]) -> Ordering;
+
+    /// Degree first, then lexicographic order of letters.
+    fn compare_words(&self, lhs: &[(usize, u64)], rhs: &[(usize, u64)]) -> Ordering {
+        let degree = |word: &[(usize, u64)]| {
+            word.iter()
+                .fold(0, |degree: u64, (_, power)| degree.saturating_add(*power))
+        };
+        degree(lhs)
+            .cmp(&degree(rhs))
+            .then_with(|| compare_letters(rhs, lhs))
+    }
+}
+
+/// Lexicographic order of exponents.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Lex;
+
+impl MonomialOrder for Lex {
+    fn compare(&self, lhs: &[u64], rhs: &[u64]) -> Ordering {
+        lhs.cmp(rhs)
+    }
+}
+
+/// Total degree first, then lexicographic order.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct GrLex;
+
+impl MonomialOrder for GrLex {
+    fn compare(&self, lhs: &[u64], rhs: &[u64]) -> Ordering {
+        degree(lhs).cmp(&degree(rhs)).then_with(|| lhs.cmp(rhs))
+    }
+}
+
+/// Total degree first, then the smaller exponent of the last symbol where the monomials differ.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct GrevLex;
+
+impl MonomialOrder for GrevLex {
+    fn compare(&self, lhs: &[u64], rhs: &[u64]) -> Ordering {
+        degree(lhs).cmp(&degree(rhs)).then_with(|| {
+            lhs.iter()
+                .zip(rhs)
+                .rev()
+                .map(|(lhs, rhs)| rhs.cmp(lhs))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+fn degree(exponents: &[u64]) -> u64 {
+    exponents.iter().sum()
+}
+
+// Lexicographic order of run-length encoded words, as if their runs were expanded.
+fn compare_letters(lhs: &[(usize, u64)], rhs: &[(usize, u64)]) -> Ordering {
+    let (mut lhs, mut rhs) = (lhs.iter().copied(), rhs.iter().copied());
+    let (mut lhs_run, mut rhs_run) = (lhs.next(), rhs.next());
+    loop {
+        match (lhs_run, rhs_run) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some((lhs_letter, _)), Some((rhs_letter, _))) if lhs_letter != rhs_letter => {
+                return lhs_letter.cmp(&rhs_letter)
+            }
+            // the shorter run ends first, the rest of the longer one is compared with what follows it
+            (Some((letter, lhs_power)), Some((_, rhs_power))) => match lhs_power.cmp(&rhs_power) {
+                Ordering::Less => {
+                    lhs_run = lhs.next();
+                    rhs_run = Some((letter, rhs_power - lhs_power));
+                }
+                Ordering::Equal => {
+                    lhs_run = lhs.next();
+                    rhs_run = rhs.next();
+                }
+                Ordering::Greater => {
+                    lhs_run = Some((letter, lhs_power - rhs_power));
+                    rhs_run = rhs.next();
+                }
+            },
+        }
+    }
+}
+
 pub trait PolyTypes<Types: PolyTypes<Types>>
 where
-    Self: Sized + Display + Debug + Clone + Eq,
+    Self: Sized + Display + Debug + Clone + Eq + ThreadSafe,
     Self: Mul<Output = MulTraits<Types>>,
     Self: Add<Output = Option<Self>>, // TODO: this is not checking commutative in any kind
     Self: Neg<Output = Self>,
 {
+    fn power_strategy(&self) -> PowerStrategy {
+        PowerStrategy::Binary
+    }
 }
 
 pub trait PolyValues<Types: PolyTypes<Types>, Values: PolyValues<Types, Values>>
 where
-    Self: Sized + Display + Debug + Clone,
+    Self: Sized + Display + Debug + Clone + ThreadSafe,
     Self: Mul<Output = Option<Self>>,
     Self: Add<Output = Option<Self>>,
     Self: Neg<Output = Self>,
@@ -37,4 +171,53 @@ where
     fn one_with_type(expected_type: Types) -> Option<Self>;
 
     fn as_type(&self) -> Types;
+
+    /// LaTeX representation of the value, used by `Polynomial::to_latex`.
+    fn to_latex(&self) -> String {
+        self.to_string()
+    }
+
+    /// Representation of the value in the syntax of a computer algebra system.
+    fn to_cas(&self, _syntax: CasSyntax) -> String {
+        self.to_string()
+    }
+
+    /// Whether the value is a unit, so it may be omitted as a coefficient.
+    fn is_one(&self) -> bool {
+        false
+    }
+
+    /// Whether the value should be printed as subtraction of its negation (see `PolyFormatter`).
+    fn is_negative(&self) -> bool {
+        false
+    }
+
+    /// Fast power used by `PowerStrategy::Custom` (for example through diagonalization).
+    fn pow(self, _power: u64) -> Option<Self> {
+        None
+    }
+}
+
+/// Values that can be divided by positive integers, required by `Polynomial::integrate`.
+pub trait DivideByNatural: Sized {
+    /// `self / divisor`, `None` if the quotient is not a value of the domain.
+    fn divide_by_natural(&self, divisor: u64) -> Option<Self>;
+}
+
+/// Source of random values for `Polynomial::probably_equal`.
+pub trait Sampler<Values, Types> {
+    /// Random value of `value_type` (for matrices also of a suitable shape),
+    /// `None` if the type is not supported.
+    fn sample(&mut self, value_type: &Types) -> Option<Values>;
+}
+
+/// Values with a zero test, required by the univariate algorithms (see `Polynomial::div_rem`).
+pub trait IsZero {
+    fn is_zero(&self) -> bool;
+}
+
+/// Values of a field, required by the univariate algorithms (see `Polynomial::div_rem`).
+pub trait Field: Sized {
+    /// Multiplicative inverse, `None` for zero.
+    fn inverse(&self) -> Option<Self>;
 }
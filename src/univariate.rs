@@ -0,0 +1,362 @@
+use crate::{
+    error::UnivariateError,
+    factor::{Factor, SubPoly},
+    polynomial::{Polynomial, PolynomialBuilder},
+    symbol::Symbol,
+    term::Term,
+    traits::{Field, IsZero, PolyTypes, PolyValues},
+};
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero,
+{
+    /// Dense view of the polynomial as a univariate one in `symbol`:
+    /// coefficients of `symbol^0`, `symbol^1` and so on up to the last non-zero one (none for zero).
+    ///
+    /// The polynomial must not contain other symbols, the type of `symbol` must be closed under
+    /// commutative multiplication and every value must have it. Sub-polynomials are expanded.
+    pub fn univariate_coefficients(
+        &self,
+        symbol: &Symbol<Types>,
+    ) -> Result<Vec<Values>, UnivariateError> {
+        Univariate::new(symbol)?.sub_poly(self.sub_poly())
+    }
+}
+
+impl<Values, Types> Polynomial<Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero + Field,
+{
+    /// Division with remainder of univariate polynomials in `symbol` (see `univariate_coefficients`):
+    /// `self = quotient·divisor + remainder` where the remainder has smaller degree than the divisor.
+    pub fn div_rem(
+        &self,
+        divisor: &Self,
+        symbol: &Symbol<Types>,
+    ) -> Result<(Self, Self), UnivariateError> {
+        let univariate = Univariate::new(symbol)?;
+        let (quotient, remainder) = univariate.div_rem(
+            univariate.sub_poly(self.sub_poly())?,
+            &univariate.sub_poly(divisor.sub_poly())?,
+        )?;
+        Ok((
+            univariate.polynomial(quotient),
+            univariate.polynomial(remainder),
+        ))
+    }
+
+    /// Monic greatest common divisor of univariate polynomials in `symbol`
+    /// computed with Euclid's algorithm, zero if both polynomials are zero.
+    pub fn gcd(&self, other: &Self, symbol: &Symbol<Types>) -> Result<Self, UnivariateError> {
+        let univariate = Univariate::new(symbol)?;
+        let gcd = univariate.gcd(
+            univariate.sub_poly(self.sub_poly())?,
+            univariate.sub_poly(other.sub_poly())?,
+        )?;
+        Ok(univariate.polynomial(gcd))
+    }
+
+    /// Same as `gcd`, but computed with the subresultant remainder sequence,
+    /// which uses pseudo-division and keeps intermediate coefficients small in fraction fields.
+    pub fn subresultant_gcd(
+        &self,
+        other: &Self,
+        symbol: &Symbol<Types>,
+    ) -> Result<Self, UnivariateError> {
+        let univariate = Univariate::new(symbol)?;
+        let gcd = univariate.subresultant_gcd(
+            univariate.sub_poly(self.sub_poly())?,
+            univariate.sub_poly(other.sub_poly())?,
+        )?;
+        Ok(univariate.polynomial(gcd))
+    }
+
+    /// Monic least common multiple of univariate polynomials in `symbol`,
+    /// zero if any of the polynomials is zero.
+    pub fn lcm(&self, other: &Self, symbol: &Symbol<Types>) -> Result<Self, UnivariateError> {
+        let univariate = Univariate::new(symbol)?;
+        let lhs = univariate.sub_poly(self.sub_poly())?;
+        let rhs = univariate.sub_poly(other.sub_poly())?;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Ok(univariate.polynomial(vec![]));
+        }
+        let gcd = univariate.gcd(lhs.clone(), rhs.clone())?;
+        let (lcm, _) = univariate.div_rem(univariate.mul(&lhs, &rhs)?, &gcd)?;
+        Ok(univariate.polynomial(univariate.monic(lcm)?))
+    }
+}
+
+// Dense polynomials are coefficients in ascending order of powers without trailing zeros.
+struct Univariate<'a, Values, Types> {
+    symbol: &'a Symbol<Types>,
+    symbol_type: Types,
+    zero: Values,
+    one: Values,
+}
+
+impl<'a, Values, Types> Univariate<'a, Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero,
+{
+    fn new(symbol: &'a Symbol<Types>) -> Result<Self, UnivariateError> {
+        let symbol_type = symbol
+            .associated_type
+            .clone()
+            .ok_or_else(|| UnivariateError::UntypedSymbol(symbol.label.clone()))?;
+        let traits = symbol_type.clone() * symbol_type.clone();
+        let unsupported = || UnivariateError::UnsupportedType(symbol_type.to_string());
+        if !traits.commutative || traits.result.as_ref() != Some(&symbol_type) {
+            return Err(unsupported());
+        }
+        Ok(Univariate {
+            symbol,
+            zero: Values::zero_with_type(symbol_type.clone()).ok_or_else(unsupported)?,
+            one: Values::one_with_type(symbol_type.clone()).ok_or_else(unsupported)?,
+            symbol_type,
+        })
+    }
+
+    // arithmetic within a closed type is expected to succeed
+    fn value(&self, value: Option<Values>) -> Result<Values, UnivariateError> {
+        value.ok_or_else(|| UnivariateError::UnsupportedType(self.symbol_type.to_string()))
+    }
+
+    fn sub_poly(&self, poly: &SubPoly<Values, Types>) -> Result<Vec<Values>, UnivariateError> {
+        poly.terms().iter().try_fold(vec![], |sum, term| {
+            let term = self.term(term)?;
+            self.add(&sum, &term)
+        })
+    }
+
+    fn term(&self, term: &Term<Values, Types>) -> Result<Vec<Values>, UnivariateError> {
+        let mut product = self.trim(vec![self.constant(term.coefficient())?]);
+        for factor in term.factors() {
+            let base = match factor.factor() {
+                Factor::Value(value) => vec![self.constant(value.value())?],
+                Factor::Variable(variable) if variable.symbol() == self.symbol => {
+                    vec![self.zero.clone(), self.one.clone()]
+                }
+                Factor::Variable(variable) => {
+                    return Err(UnivariateError::NotUnivariate(
+                        variable.symbol().label.clone(),
+                    ))
+                }
+                Factor::SubPoly(poly) => self.sub_poly(poly)?,
+            };
+            for _ in 0..factor.power() {
+                product = self.mul(&product, &base)?;
+            }
+        }
+        Ok(product)
+    }
+
+    fn constant(&self, value: &Values) -> Result<Values, UnivariateError> {
+        match value.as_type() == self.symbol_type {
+            true => Ok(value.clone()),
+            false => Err(UnivariateError::UnsupportedType(
+                value.as_type().to_string(),
+            )),
+        }
+    }
+
+    fn polynomial(&self, coefficients: Vec<Values>) -> Polynomial<Values, Types> {
+        if coefficients.is_empty() {
+            return Polynomial::zero(self.symbol_type.clone())
+                .expect("zero of the symbol type is checked on creation");
+        }
+        let mut builder = PolynomialBuilder::new();
+        for (power, coefficient) in coefficients.into_iter().enumerate().rev() {
+            if coefficient.is_zero() {
+                continue;
+            }
+            let term_builder = builder.term_builder(coefficient);
+            builder = match power {
+                0 => term_builder.build(),
+                power => term_builder
+                    .variable(self.symbol.clone(), power as u64)
+                    .build(),
+            };
+        }
+        builder
+            .build()
+            .expect("terms of a dense polynomial are always built")
+    }
+
+    fn trim(&self, mut poly: Vec<Values>) -> Vec<Values> {
+        while poly.last().is_some_and(IsZero::is_zero) {
+            poly.pop();
+        }
+        poly
+    }
+
+    fn add(&self, lhs: &[Values], rhs: &[Values]) -> Result<Vec<Values>, UnivariateError> {
+        let sum = (0..lhs.len().max(rhs.len()))
+            .map(|index| match (lhs.get(index), rhs.get(index)) {
+                (Some(lhs), Some(rhs)) => self.value(lhs.clone() + rhs.clone()),
+                (Some(coefficient), None) | (None, Some(coefficient)) => Ok(coefficient.clone()),
+                (None, None) => unreachable!("index is below the longest length"),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.trim(sum))
+    }
+
+    fn sub(&self, lhs: &[Values], rhs: &[Values]) -> Result<Vec<Values>, UnivariateError> {
+        let negated = rhs
+            .iter()
+            .map(|coefficient| -coefficient.clone())
+            .collect::<Vec<_>>();
+        self.add(lhs, &negated)
+    }
+
+    fn mul(&self, lhs: &[Values], rhs: &[Values]) -> Result<Vec<Values>, UnivariateError> {
+        if lhs.is_empty() || rhs.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut product = vec![self.zero.clone(); lhs.len() + rhs.len() - 1];
+        for (lhs_power, lhs_coefficient) in lhs.iter().enumerate() {
+            for (rhs_power, rhs_coefficient) in rhs.iter().enumerate() {
+                let monomial = self.value(lhs_coefficient.clone() * rhs_coefficient.clone())?;
+                let sum = &mut product[lhs_power + rhs_power];
+                *sum = self.value(sum.clone() + monomial)?;
+            }
+        }
+        Ok(self.trim(product))
+    }
+
+    // `factor·symbol^shift·poly`
+    fn scale(
+        &self,
+        poly: &[Values],
+        factor: &Values,
+        shift: usize,
+    ) -> Result<Vec<Values>, UnivariateError> {
+        let mut scaled = vec![self.zero.clone(); shift];
+        for coefficient in poly {
+            scaled.push(self.value(factor.clone() * coefficient.clone())?);
+        }
+        Ok(self.trim(scaled))
+    }
+
+    fn power(&self, value: &Values, power: usize) -> Result<Values, UnivariateError> {
+        (0..power).try_fold(self.one.clone(), |product, _| {
+            self.value(product * value.clone())
+        })
+    }
+
+    // `lhs - factor·symbol^shift·rhs` where the leading coefficient cancels,
+    // it is dropped explicitly in case `is_zero` is approximate
+    fn cancel(
+        &self,
+        lhs: &[Values],
+        rhs: &[Values],
+        factor: &Values,
+        shift: usize,
+    ) -> Result<Vec<Values>, UnivariateError> {
+        let mut difference = self.sub(lhs, &self.scale(rhs, factor, shift)?)?;
+        difference.truncate(lhs.len() - 1);
+        Ok(self.trim(difference))
+    }
+}
+
+impl<Values, Types> Univariate<'_, Values, Types>
+where
+    Types: PolyTypes<Types>,
+    Values: PolyValues<Types, Values> + IsZero + Field,
+{
+    fn inverse(&self, value: &Values) -> Result<Values, UnivariateError> {
+        value.inverse().ok_or(UnivariateError::DivisionByZero)
+    }
+
+    fn div_rem(
+        &self,
+        dividend: Vec<Values>,
+        divisor: &[Values],
+    ) -> Result<(Vec<Values>, Vec<Values>), UnivariateError> {
+        let leading = divisor.last().ok_or(UnivariateError::DivisionByZero)?;
+        let inverse = self.inverse(leading)?;
+        let mut quotient = vec![];
+        let mut remainder = dividend;
+        while remainder.len() >= divisor.len() {
+            let shift = remainder.len() - divisor.len();
+            let factor = self.value(remainder[remainder.len() - 1].clone() * inverse.clone())?;
+            if quotient.is_empty() {
+                quotient = vec![self.zero.clone(); shift + 1];
+            }
+            remainder = self.cancel(&remainder, divisor, &factor, shift)?;
+            quotient[shift] = factor;
+        }
+        Ok((self.trim(quotient), remainder))
+    }
+
+    fn monic(&self, poly: Vec<Values>) -> Result<Vec<Values>, UnivariateError> {
+        match poly.last() {
+            Some(leading) => self.scale(&poly, &self.inverse(leading)?, 0),
+            None => Ok(poly),
+        }
+    }
+
+    fn gcd(
+        &self,
+        mut lhs: Vec<Values>,
+        mut rhs: Vec<Values>,
+    ) -> Result<Vec<Values>, UnivariateError> {
+        while !rhs.is_empty() {
+            let (_, remainder) = self.div_rem(lhs, &rhs)?;
+            lhs = rhs;
+            rhs = remainder;
+        }
+        self.monic(lhs)
+    }
+
+    // `leading(rhs)^(deg lhs - deg rhs + 1)·lhs` modulo `rhs`, computed without inverses
+    fn pseudo_remainder(
+        &self,
+        lhs: &[Values],
+        rhs: &[Values],
+    ) -> Result<Vec<Values>, UnivariateError> {
+        let leading = &rhs[rhs.len() - 1];
+        let mut steps = lhs.len() + 1 - rhs.len();
+        let mut remainder = lhs.to_vec();
+        while remainder.len() >= rhs.len() {
+            let shift = remainder.len() - rhs.len();
+            let factor = remainder[remainder.len() - 1].clone();
+            remainder = self.cancel(&self.scale(&remainder, leading, 0)?, rhs, &factor, shift)?;
+            steps -= 1;
+        }
+        self.scale(&remainder, &self.power(leading, steps)?, 0)
+    }
+
+    fn subresultant_gcd(
+        &self,
+        lhs: Vec<Values>,
+        rhs: Vec<Values>,
+    ) -> Result<Vec<Values>, UnivariateError> {
+        let (mut lhs, mut rhs) = match lhs.len() >= rhs.len() {
+            true => (lhs, rhs),
+            false => (rhs, lhs),
+        };
+        let mut g = self.one.clone();
+        let mut h = self.one.clone();
+        while !rhs.is_empty() {
+            let delta = lhs.len() - rhs.len();
+            let remainder = self.pseudo_remainder(&lhs, &rhs)?;
+            // the remainder is divisible by `g·h^delta`
+            let divisor = self.value(g * self.power(&h, delta)?)?;
+            lhs = rhs;
+            rhs = self.scale(&remainder, &self.inverse(&divisor)?, 0)?;
+            g = lhs[lhs.len() - 1].clone();
+            // `h^(1 - delta)·g^delta`
+            h = match delta {
+                0 => h,
+                delta => {
+                    self.value(self.power(&g, delta)? * self.power(&self.inverse(&h)?, delta - 1)?)?
+                }
+            };
+        }
+        self.monic(lhs)
+    }
+}
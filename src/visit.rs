@@ -0,0 +1,82 @@
+use crate::{
+    factor::{Factor, SubPoly, Value, Variable},
+    polynomial::Polynomial,
+    term::{MonomialFactor, Term},
+};
+
+/// Read-only walk over the polynomial tree (see `Polynomial::visit`).
+///
+/// Nodes are visited depth-first in the order they are printed:
+/// `pre_*` hook of a node is called before its children and `post_*` hook after them.
+/// Every hook does nothing by default, so a visitor implements only the ones it needs.
+pub trait PolyVisitor<Values, Types> {
+    fn pre_sub_poly(&mut self, _sub_poly: &SubPoly<Values, Types>) {}
+    fn post_sub_poly(&mut self, _sub_poly: &SubPoly<Values, Types>) {}
+
+    fn pre_term(&mut self, _term: &Term<Values, Types>) {}
+    fn post_term(&mut self, _term: &Term<Values, Types>) {}
+
+    fn pre_monomial_factor(&mut self, _factor: &MonomialFactor<Values, Types>) {}
+    fn post_monomial_factor(&mut self, _factor: &MonomialFactor<Values, Types>) {}
+
+    fn pre_value(&mut self, _value: &Value<Values, Types>) {}
+    fn post_value(&mut self, _value: &Value<Values, Types>) {}
+
+    fn pre_variable(&mut self, _variable: &Variable<Types>) {}
+    fn post_variable(&mut self, _variable: &Variable<Types>) {}
+}
+
+impl<Values, Types> Polynomial<Values, Types> {
+    /// Walks the whole tree with `visitor`, the root is the `SubPoly` of the polynomial.
+    pub fn visit<Visitor>(&self, visitor: &mut Visitor)
+    where
+        Visitor: PolyVisitor<Values, Types> + ?Sized,
+    {
+        self.sub_poly().visit(visitor);
+    }
+}
+
+impl<Values, Types> SubPoly<Values, Types> {
+    pub fn visit<Visitor>(&self, visitor: &mut Visitor)
+    where
+        Visitor: PolyVisitor<Values, Types> + ?Sized,
+    {
+        visitor.pre_sub_poly(self);
+        self.terms().iter().for_each(|term| term.visit(visitor));
+        visitor.post_sub_poly(self);
+    }
+}
+
+impl<Values, Types> Term<Values, Types> {
+    pub fn visit<Visitor>(&self, visitor: &mut Visitor)
+    where
+        Visitor: PolyVisitor<Values, Types> + ?Sized,
+    {
+        visitor.pre_term(self);
+        self.factors()
+            .iter()
+            .for_each(|factor| factor.visit(visitor));
+        visitor.post_term(self);
+    }
+}
+
+impl<Values, Types> MonomialFactor<Values, Types> {
+    pub fn visit<Visitor>(&self, visitor: &mut Visitor)
+    where
+        Visitor: PolyVisitor<Values, Types> + ?Sized,
+    {
+        visitor.pre_monomial_factor(self);
+        match self.factor() {
+            Factor::Value(value) => {
+                visitor.pre_value(value);
+                visitor.post_value(value);
+            }
+            Factor::Variable(variable) => {
+                visitor.pre_variable(variable);
+                visitor.post_variable(variable);
+            }
+            Factor::SubPoly(sub_poly) => sub_poly.visit(visitor),
+        }
+        visitor.post_monomial_factor(self);
+    }
+}
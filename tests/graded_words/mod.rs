@@ -0,0 +1,4 @@
+// Words over some alphabet with concatenation as multiplication and as (non-commutative) addition.
+// Type of a word is its length, so multiplication is associative, but no type (except empty word) is closed under it.
+pub mod types;
+pub mod values;
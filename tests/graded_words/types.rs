@@ -0,0 +1,56 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg},
+};
+
+use poly_gnom::traits::{MulTraits, PolyTypes, PowerStrategy};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Types {
+    pub length: usize,
+}
+
+impl Types {
+    pub fn new(length: usize) -> Self {
+        Types { length }
+    }
+}
+
+impl Neg for Types {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl Add for Types {
+    type Output = Option<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Some(Types::new(self.length + rhs.length))
+    }
+}
+
+impl Mul for Types {
+    type Output = MulTraits<Self>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        MulTraits {
+            result: Some(Types::new(self.length + rhs.length)),
+            commutative: self.length == 0 || rhs.length == 0,
+        }
+    }
+}
+
+impl Display for Types {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Word{}", self.length)
+    }
+}
+
+impl PolyTypes<Types> for Types {
+    fn power_strategy(&self) -> PowerStrategy {
+        PowerStrategy::Repeated
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Add, Mul, Neg},
+};
+
+use poly_gnom::traits::PolyValues;
+
+use super::types::Types;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Values {
+    word: String,
+}
+
+impl Values {
+    pub fn new(word: &str) -> Self {
+        Values {
+            word: word.to_owned(),
+        }
+    }
+}
+
+impl Add for Values {
+    type Output = Option<Values>;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.word.push_str(&rhs.word);
+        Some(self)
+    }
+}
+
+impl Mul for Values {
+    type Output = Option<Values>;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self.word.push_str(&rhs.word);
+        Some(self)
+    }
+}
+
+impl Neg for Values {
+    type Output = Values;
+
+    // there are no inverses for concatenation, so negation is not meaningful
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl Debug for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.word)
+    }
+}
+
+impl Display for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.word)
+    }
+}
+
+impl PolyValues<Types, Values> for Values {
+    fn zero_with_type(_: Types) -> Option<Self> {
+        None
+    }
+
+    fn one_with_type(expected_type: Types) -> Option<Self> {
+        match expected_type.length {
+            0 => Some(Values::new("")),
+            _ => None,
+        }
+    }
+
+    fn as_type(&self) -> Types {
+        Types::new(self.word.chars().count())
+    }
+}
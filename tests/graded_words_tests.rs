@@ -0,0 +1,103 @@
+mod graded_words;
+
+mod tests {
+    use crate::graded_words::{types::Types, values::Values};
+    use poly_gnom::{
+        polynomial::Polynomial,
+        symbol::{SymbolInfo, SymbolsProvider},
+    };
+
+    fn basic_symbols_provider() -> SymbolsProvider<Types> {
+        let provider = SymbolsProvider::empty();
+
+        provider.add(SymbolInfo::new_typed("a", Types::new(1)));
+        provider.add(SymbolInfo::new_typed("b", Types::new(2)));
+        provider.add(SymbolInfo::new("w", None));
+
+        provider
+    }
+
+    type WordsPolynomial = Polynomial<Values, Types>;
+
+    #[test]
+    fn repeated_power_test() {
+        let provider = basic_symbols_provider();
+
+        let poly = WordsPolynomial::builder()
+            .term_builder(Values::new(""))
+            .variable(provider.get("a").unwrap(), 3)
+            .variable(provider.get("b").unwrap(), 2)
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(poly.as_type().unwrap(), Types::new(7));
+
+        let value = poly
+            .substitute_value(provider.get("a").unwrap(), Values::new("x"))
+            .substitute_value(provider.get("b").unwrap(), Values::new("yz"))
+            .as_value()
+            .unwrap();
+
+        assert_eq!(value, Values::new("xxxyzyz"));
+    }
+
+    #[test]
+    fn zero_power_test() {
+        let provider = basic_symbols_provider();
+
+        let poly = WordsPolynomial::builder()
+            .term_builder(Values::new("c"))
+            .variable(provider.get("w").unwrap(), 0)
+            .build()
+            .build()
+            .unwrap();
+
+        let empty = poly
+            .clone()
+            .substitute_value(provider.get("w").unwrap(), Values::new(""))
+            .as_value();
+        assert_eq!(empty.ok(), Some(Values::new("c")));
+
+        let non_empty = poly
+            .substitute_value(provider.get("w").unwrap(), Values::new("ab"))
+            .as_value();
+        assert!(non_empty.is_err());
+    }
+
+    // With the `parallel` feature terms and factors are evaluated by rayon,
+    // but both sums and products of words must still be concatenated in order.
+    #[test]
+    fn ordered_evaluation_test() {
+        let provider = basic_symbols_provider();
+        let a = provider.get("a").unwrap();
+        let b = provider.get("b").unwrap();
+
+        let letters = "abcdefghijklmnopqrstuvwxyz";
+        let mut builder = WordsPolynomial::builder();
+        for letter in letters.chars() {
+            builder = builder
+                .term_builder(Values::new(&letter.to_string()))
+                .variable(a.clone(), 1)
+                .value(Values::new(&letter.to_uppercase().to_string()), 1)
+                .variable(b.clone(), 1)
+                .build();
+        }
+        let poly = builder.build().unwrap();
+        assert_eq!(poly.as_type().unwrap(), Types::new(26 * 5));
+
+        let expected = letters
+            .chars()
+            .map(|letter| format!("{}0{}12", letter, letter.to_uppercase()))
+            .collect::<String>();
+        for _ in 0..8 {
+            let value = poly
+                .clone()
+                .substitute_value(a.clone(), Values::new("0"))
+                .substitute_value(b.clone(), Values::new("12"))
+                .as_value()
+                .unwrap();
+            assert_eq!(value, Values::new(&expected));
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// Integers modulo a prime with the usual addition and multiplication, the simplest field.
+// There is a single type, which is closed under commutative multiplication.
+pub mod types;
+pub mod values;
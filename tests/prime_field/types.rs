@@ -0,0 +1,44 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg},
+};
+
+use poly_gnom::traits::{MulTraits, PolyTypes};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Types;
+
+impl Neg for Types {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl Add for Types {
+    type Output = Option<Self>;
+
+    fn add(self, _: Self) -> Self::Output {
+        Some(Types)
+    }
+}
+
+impl Mul for Types {
+    type Output = MulTraits<Self>;
+
+    fn mul(self, _: Self) -> Self::Output {
+        MulTraits {
+            result: Some(Types),
+            commutative: true,
+        }
+    }
+}
+
+impl Display for Types {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "F{}", super::values::MODULUS)
+    }
+}
+
+impl PolyTypes<Types> for Types {}
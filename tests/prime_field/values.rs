@@ -0,0 +1,103 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Add, Mul, Neg},
+};
+
+use poly_gnom::traits::{Field, IsZero, PolyValues};
+
+use super::types::Types;
+
+pub const MODULUS: u64 = 101;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Values {
+    residue: u64,
+}
+
+impl Values {
+    pub fn new(value: i64) -> Self {
+        Values {
+            residue: value.rem_euclid(MODULUS as i64) as u64,
+        }
+    }
+}
+
+impl Add for Values {
+    type Output = Option<Values>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Some(Values {
+            residue: (self.residue + rhs.residue) % MODULUS,
+        })
+    }
+}
+
+impl Mul for Values {
+    type Output = Option<Values>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Some(Values {
+            residue: self.residue * rhs.residue % MODULUS,
+        })
+    }
+}
+
+impl Neg for Values {
+    type Output = Values;
+
+    fn neg(self) -> Self::Output {
+        Values {
+            residue: (MODULUS - self.residue) % MODULUS,
+        }
+    }
+}
+
+impl Debug for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.residue)
+    }
+}
+
+impl Display for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.residue)
+    }
+}
+
+impl PolyValues<Types, Values> for Values {
+    fn zero_with_type(_: Types) -> Option<Self> {
+        Some(Values::new(0))
+    }
+
+    fn one_with_type(_: Types) -> Option<Self> {
+        Some(Values::new(1))
+    }
+
+    fn as_type(&self) -> Types {
+        Types
+    }
+
+    fn is_one(&self) -> bool {
+        self.residue == 1
+    }
+}
+
+impl IsZero for Values {
+    fn is_zero(&self) -> bool {
+        self.residue == 0
+    }
+}
+
+impl Field for Values {
+    // Fermat's little theorem
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut inverse = Values::new(1);
+        for _ in 0..MODULUS - 2 {
+            inverse = (inverse * *self)?;
+        }
+        Some(inverse)
+    }
+}
@@ -0,0 +1,267 @@
+mod prime_field;
+
+mod tests {
+    use crate::prime_field::{types::Types, values::Values};
+    use poly_gnom::{
+        error::{GroebnerError, UnivariateError},
+        polynomial::Polynomial,
+        polynomial_map::PolynomialMap,
+        symbol::{Symbol, SymbolInfo, SymbolsProvider},
+        traits::{GrLex, GrevLex, Lex, MonomialOrder},
+    };
+
+    fn basic_symbols_provider() -> SymbolsProvider<Types> {
+        let provider = SymbolsProvider::empty();
+
+        provider.add(SymbolInfo::new_typed("x", Types));
+        provider.add(SymbolInfo::new_typed("y", Types));
+        provider.add(SymbolInfo::new_typed("z", Types));
+
+        provider
+    }
+
+    type FieldPolynomial = Polynomial<Values, Types>;
+
+    // Sum of `coefficients[i]·x^i`.
+    fn dense(x: &Symbol<Types>, coefficients: &[i64]) -> FieldPolynomial {
+        let mut builder = FieldPolynomial::builder();
+        for (power, coefficient) in coefficients.iter().enumerate() {
+            builder = builder
+                .term_builder(Values::new(*coefficient))
+                .variable(x.clone(), power as u64)
+                .build();
+        }
+        builder.build().unwrap()
+    }
+
+    // Symbols with their powers.
+    type Monomial<'a> = &'a [(&'a Symbol<Types>, u64)];
+
+    // Sum of `coefficient·Π symbol^power` over the terms.
+    fn sparse(terms: &[(i64, Monomial)]) -> FieldPolynomial {
+        let mut builder = FieldPolynomial::builder();
+        for (coefficient, monomial) in terms {
+            let mut term_builder = builder.term_builder(Values::new(*coefficient));
+            for (symbol, power) in monomial.iter() {
+                term_builder = term_builder.variable((*symbol).clone(), *power);
+            }
+            builder = term_builder.build();
+        }
+        builder.build().unwrap()
+    }
+
+    // Product of `x - root` over the roots.
+    fn with_roots(x: &Symbol<Types>, roots: &[i64]) -> FieldPolynomial {
+        let mut term_builder = FieldPolynomial::builder().term_builder(Values::new(1));
+        for root in roots {
+            term_builder = term_builder.polynomial(dense(x, &[-root, 1]), 1);
+        }
+        term_builder.build().build().unwrap()
+    }
+
+    fn coefficients(poly: &FieldPolynomial, x: &Symbol<Types>) -> Vec<Values> {
+        poly.univariate_coefficients(x).unwrap()
+    }
+
+    fn values(coefficients: &[i64]) -> Vec<Values> {
+        coefficients
+            .iter()
+            .map(|value| Values::new(*value))
+            .collect()
+    }
+
+    #[test]
+    fn div_rem_test() {
+        let provider = basic_symbols_provider();
+        let x = provider.get("x").unwrap();
+
+        let (quotient, remainder) = with_roots(&x, &[1, 2, 3])
+            .div_rem(&with_roots(&x, &[2]), &x)
+            .unwrap();
+        assert_eq!(
+            coefficients(&quotient, &x),
+            coefficients(&with_roots(&x, &[1, 3]), &x)
+        );
+        assert!(coefficients(&remainder, &x).is_empty());
+        assert_eq!(remainder.as_value().unwrap(), Values::new(0));
+
+        // x^3 + 2 = (x^2 + x + 1)·(x - 1) + 3, with the divisor scaled by 2
+        let (quotient, remainder) = dense(&x, &[2, 0, 0, 1])
+            .div_rem(&dense(&x, &[-2, 2]), &x)
+            .unwrap();
+        let half = Values::new(51);
+        assert_eq!(coefficients(&quotient, &x), vec![half, half, half]);
+        assert_eq!(coefficients(&remainder, &x), values(&[3]));
+
+        assert_eq!(
+            dense(&x, &[1, 1]).div_rem(&dense(&x, &[0]), &x).err(),
+            Some(UnivariateError::DivisionByZero)
+        );
+        let y = provider.get("y").unwrap();
+        assert_eq!(
+            dense(&x, &[1, 1]).div_rem(&dense(&y, &[1, 1]), &x).err(),
+            Some(UnivariateError::NotUnivariate("y".to_owned()))
+        );
+        let untyped = provider.add(SymbolInfo::new("t", None));
+        assert_eq!(
+            dense(&x, &[1]).div_rem(&dense(&x, &[1]), &untyped).err(),
+            Some(UnivariateError::UntypedSymbol("t".to_owned()))
+        );
+    }
+
+    #[test]
+    fn gcd_test() {
+        let provider = basic_symbols_provider();
+        let x = provider.get("x").unwrap();
+
+        // 3·(x - 1)(x + 1)(x - 5)^2 and 7·(x - 1)(x - 5)(x - 9)
+        let lhs = FieldPolynomial::builder()
+            .term_builder(Values::new(3))
+            .polynomial(with_roots(&x, &[1, -1]), 1)
+            .polynomial(with_roots(&x, &[5]), 2)
+            .build()
+            .build()
+            .unwrap();
+        let rhs = FieldPolynomial::builder()
+            .term_builder(Values::new(7))
+            .polynomial(with_roots(&x, &[1, 5, 9]), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let expected = coefficients(&with_roots(&x, &[1, 5]), &x);
+        assert_eq!(coefficients(&lhs.gcd(&rhs, &x).unwrap(), &x), expected);
+        assert_eq!(
+            coefficients(&lhs.subresultant_gcd(&rhs, &x).unwrap(), &x),
+            expected
+        );
+        assert_eq!(
+            coefficients(&rhs.subresultant_gcd(&lhs, &x).unwrap(), &x),
+            expected
+        );
+        assert_eq!(
+            coefficients(&lhs.lcm(&rhs, &x).unwrap(), &x),
+            coefficients(&with_roots(&x, &[1, -1, 5, 5, 9]), &x)
+        );
+
+        // degrees that differ by more than one
+        let long = with_roots(&x, &[1, 2, 3, 4, 5, 6]);
+        let short = with_roots(&x, &[2, 7, 4]);
+        let expected = coefficients(&with_roots(&x, &[2, 4]), &x);
+        assert_eq!(coefficients(&long.gcd(&short, &x).unwrap(), &x), expected);
+        assert_eq!(
+            coefficients(&long.subresultant_gcd(&short, &x).unwrap(), &x),
+            expected
+        );
+
+        // coprime polynomials and zero
+        let coprime = with_roots(&x, &[2, 3]);
+        assert_eq!(
+            coefficients(&lhs.gcd(&coprime, &x).unwrap(), &x),
+            values(&[1])
+        );
+        assert_eq!(
+            coefficients(&lhs.subresultant_gcd(&coprime, &x).unwrap(), &x),
+            values(&[1])
+        );
+        let zero = dense(&x, &[0]);
+        assert_eq!(
+            coefficients(&zero.gcd(&rhs, &x).unwrap(), &x),
+            coefficients(&with_roots(&x, &[1, 5, 9]), &x)
+        );
+        assert!(coefficients(&zero.lcm(&rhs, &x).unwrap(), &x).is_empty());
+    }
+
+    #[test]
+    fn groebner_basis_test() {
+        let provider = basic_symbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let z = provider.get("z").unwrap();
+        let variables = [x.clone(), y.clone()];
+
+        // x^2 + y^2 - 1 = 0, x - y = 0
+        let system = PolynomialMap::new(
+            &provider,
+            vec![
+                sparse(&[(1, &[(&x, 2)]), (1, &[(&y, 2)]), (-1, &[])]),
+                sparse(&[(1, &[(&x, 1)]), (-1, &[(&y, 1)])]),
+            ],
+        )
+        .unwrap();
+        let basis = system.groebner_basis(&variables, &Lex).unwrap();
+        assert_eq!(basis.len(), 2);
+        // x is eliminated: y^2 - 1/2
+        let eliminated = &basis.components()[1];
+        assert!(!eliminated.contains(&x));
+        assert_eq!(
+            eliminated.univariate_coefficients(&y).unwrap(),
+            values(&[-51, 0, 1])
+        );
+
+        for order in [&Lex as &dyn MonomialOrder, &GrLex, &GrevLex] {
+            let square_difference = sparse(&[(1, &[(&x, 2)]), (-1, &[(&y, 2)])]);
+            assert!(system
+                .ideal_contains(&square_difference, &variables, order)
+                .unwrap());
+            let sum = sparse(&[(1, &[(&x, 1)]), (1, &[(&y, 1)])]);
+            assert!(!system.ideal_contains(&sum, &variables, order).unwrap());
+            let square = sparse(&[(1, &[(&x, 2)])]);
+            assert_eq!(
+                system
+                    .normal_form(&square, &variables, order)
+                    .unwrap()
+                    .as_value()
+                    .unwrap(),
+                Values::new(51)
+            );
+        }
+
+        // the basis is computed once and reused for every query
+        let groebner = system.groebner(&variables, &GrevLex).unwrap();
+        assert_eq!(groebner.to_map().len(), 2);
+        for (poly, contained) in [
+            (sparse(&[(1, &[(&x, 2)]), (-1, &[(&y, 2)])]), true),
+            (sparse(&[(1, &[(&x, 1), (&y, 1)]), (-51, &[])]), true),
+            (sparse(&[(1, &[(&x, 1)]), (1, &[(&y, 1)])]), false),
+        ] {
+            assert_eq!(groebner.contains(&poly).unwrap(), contained);
+            assert_eq!(
+                groebner.normal_form(&poly).unwrap().to_string(),
+                system
+                    .normal_form(&poly, &variables, &GrevLex)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+
+        // x^2·z = x·y^2 is reduced to different sides by different orders
+        let variables = [x.clone(), y.clone(), z.clone()];
+        let system = PolynomialMap::new(
+            &provider,
+            vec![sparse(&[
+                (1, &[(&x, 2), (&z, 1)]),
+                (-1, &[(&x, 1), (&y, 2)]),
+            ])],
+        )
+        .unwrap();
+        let lhs = sparse(&[(1, &[(&x, 2), (&z, 1)])]);
+        let rhs = sparse(&[(1, &[(&x, 1), (&y, 2)])]);
+        let normal_form = |poly: &FieldPolynomial, order: &dyn MonomialOrder| {
+            system.normal_form(poly, &variables, order).unwrap()
+        };
+        assert_eq!(normal_form(&lhs, &GrLex).degree_in(&z), 0);
+        assert_eq!(normal_form(&rhs, &GrLex).degree_in(&y), 2);
+        assert_eq!(normal_form(&lhs, &GrevLex).degree_in(&z), 1);
+        assert_eq!(normal_form(&rhs, &GrevLex).degree_in(&z), 1);
+
+        assert_eq!(
+            system.groebner_basis(&variables[..2], &Lex).err(),
+            Some(GroebnerError::UnknownSymbol("z".to_owned()))
+        );
+        assert_eq!(
+            system.groebner_basis(&[], &Lex).err(),
+            Some(GroebnerError::NoVariables)
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use poly_gnom::codegen::{CValueCodegen, ValueCodegen};
+
+use super::{types::Types, values::Values};
+
+// Scalars are plain `i64`, vectors and matrices (stored by rows) are `Vec`s.
+// Everything except scalar products is done with helper functions like `mul_matrix_vector`,
+// that are expected to be defined next to the generated code.
+pub struct RustCodegen;
+
+fn kind(value_type: &Types) -> &'static str {
+    match value_type {
+        Types::Scalar => "scalar",
+        Types::Vector => "vector",
+        Types::Matrix => "matrix",
+    }
+}
+
+impl ValueCodegen<Values, Types> for RustCodegen {
+    fn rust_type(&self, value_type: &Types) -> String {
+        match value_type {
+            Types::Scalar => "i64",
+            Types::Vector => "Vec<i64>",
+            Types::Matrix => "Vec<Vec<i64>>",
+        }
+        .to_owned()
+    }
+
+    fn constant(&self, value: &Values) -> String {
+        match value {
+            Values::Scalar(scalar) => scalar.to_rust(),
+            Values::Vector(vector) => vector.to_rust(),
+            Values::Matrix(matrix) => matrix.to_rust(),
+        }
+    }
+
+    fn mul(&self, lhs: &str, lhs_type: &Types, rhs: &str, rhs_type: &Types) -> String {
+        match (lhs_type, rhs_type) {
+            (Types::Scalar, Types::Scalar) => format!("{} * {}", lhs, rhs),
+            _ => format!(
+                "mul_{}_{}(&{}, &{})",
+                kind(lhs_type),
+                kind(rhs_type),
+                lhs,
+                rhs
+            ),
+        }
+    }
+
+    fn add(&self, lhs: &str, lhs_type: &Types, rhs: &str, _rhs_type: &Types) -> String {
+        match lhs_type {
+            Types::Scalar => format!("{} + {}", lhs, rhs),
+            _ => format!("add_{}(&{}, &{})", kind(lhs_type), lhs, rhs),
+        }
+    }
+}
+
+// Only scalars can be generated as C.
+pub struct CCodegen;
+
+impl CValueCodegen<Values> for CCodegen {
+    fn c_type(&self) -> String {
+        "long long".to_owned()
+    }
+
+    fn constant(&self, value: &Values) -> String {
+        match value {
+            Values::Scalar(scalar) => format!("{}LL", scalar.to_rust()),
+            _ => unreachable!("only scalars are generated as C"),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Output of `RustCodegen` compiled as a part of the tests, so `rust_codegen_test` can check
+// that the generated code builds and computes the same values as `as_value`.
+// `model.rs` is exactly what the test expects `to_rust_fn` to produce.
+
+#![allow(clippy::let_and_return)]
+
+use super::objects::Int;
+
+fn mul_matrix_scalar(matrix: &[Vec<Int>], scalar: &Int) -> Vec<Vec<Int>> {
+    matrix
+        .iter()
+        .map(|row| row.iter().map(|element| element * scalar).collect())
+        .collect()
+}
+
+fn mul_matrix_vector(matrix: &[Vec<Int>], vector: &[Int]) -> Vec<Int> {
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(vector).map(|(lhs, rhs)| lhs * rhs).sum())
+        .collect()
+}
+
+fn mul_vector_scalar(vector: &[Int], scalar: &Int) -> Vec<Int> {
+    vector.iter().map(|element| element * scalar).collect()
+}
+
+fn add_vector(lhs: &[Int], rhs: &[Int]) -> Vec<Int> {
+    lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs + rhs).collect()
+}
+
+include!("model.rs");
@@ -1,3 +1,5 @@
+pub mod codegen;
+pub mod generated;
 pub mod objects;
 pub mod types;
 pub mod values;
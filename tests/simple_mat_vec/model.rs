@@ -0,0 +1,12 @@
+#[allow(non_snake_case)]
+pub fn model(x: i64, y: i64, A: Vec<Vec<i64>>, v: Vec<i64>, _z: i64) -> Vec<i64> {
+    let t0: i64 = x + y;
+    let t1: i64 = t0 * t0;
+    let t2: Vec<Vec<i64>> = mul_matrix_scalar(&A, &t1);
+    let t3: Vec<i64> = mul_matrix_vector(&t2, &v);
+    let t4: i64 = 2;
+    let t5: Vec<i64> = mul_vector_scalar(&t3, &t4);
+    let t6: Vec<i64> = mul_vector_scalar(&v, &t1);
+    let t7: Vec<i64> = add_vector(&t5, &t6);
+    t7
+}
@@ -3,21 +3,26 @@ use std::{
     ops::{Add, Mul, Neg},
 };
 
-use poly_gnom::traits::{One, Zero};
+#[cfg(feature = "json")]
+use poly_gnom::json::JsonValue;
+use poly_gnom::traits::{CasSyntax, One, Zero};
 
 pub type Int = i64;
 
 // TODO: Implement custom Debug
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scalar(Int);
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector {
     elements: Vec<Int>, // maybe replace with Box<[Int]>
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix {
     elements: Vec<Int>, // maybe replace with Box<[Int]>
     dimensions: (usize, usize),
@@ -35,11 +40,19 @@ impl Scalar {
     pub fn new(value: Int) -> Self {
         Scalar(value)
     }
+
+    pub fn pow(self, power: u64) -> Self {
+        Scalar(self.0.pow(power.try_into().unwrap()))
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
 }
 
 impl Vector {
     pub fn new(elements: Vec<Int>) -> Self {
-        assert!(elements.len() > 0);
+        assert!(!elements.is_empty());
         Vector { elements }
     }
 }
@@ -176,7 +189,7 @@ impl Mul<Vector> for Matrix {
             .chunks(m)
             .map(|row| {
                 assert!(row.len() == m);
-                row.into_iter()
+                row.iter()
                     .zip(rhs.elements.as_slice())
                     .fold(0, |sum, (a, b)| sum + a * b)
             })
@@ -202,7 +215,7 @@ impl Mul for Matrix {
         for i in 0..n {
             for p in 0..k {
                 for j in 0..m {
-                    elements[i * n + j] += self.elements[i * k + p] + rhs.elements[p * k + j];
+                    elements[i * m + j] += self.elements[i * k + p] * rhs.elements[p * m + j];
                 }
             }
         }
@@ -236,7 +249,7 @@ impl Add for Vector {
         let elements = self
             .elements
             .into_iter()
-            .zip(rhs.elements.into_iter())
+            .zip(rhs.elements)
             .map(|(a, b)| a + b)
             .collect::<Vec<_>>();
         assert!(elements.len() == n);
@@ -255,7 +268,7 @@ impl Add for Matrix {
         let elements = self
             .elements
             .into_iter()
-            .zip(rhs.elements.into_iter())
+            .zip(rhs.elements)
             .map(|(a, b)| a + b)
             .collect::<Vec<_>>();
         assert!(elements.len() == n * m);
@@ -300,7 +313,7 @@ impl Debug for Matrix {
             .chunks(m)
             .enumerate()
             .try_for_each(|(i, row)| {
-                assert!(row.len() == n);
+                assert!(row.len() == m);
                 write!(f, "[")?;
                 row.iter().enumerate().try_for_each(|(j, x)| {
                     write!(f, "{}", x)?;
@@ -343,3 +356,189 @@ impl Display for Matrix {
 }
 
 // DISPLAY end
+
+// LATEX begin
+
+impl Scalar {
+    pub fn is_one(&self) -> bool {
+        self.0 == 1
+    }
+
+    pub fn to_latex(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Vector {
+    pub fn to_latex(&self) -> String {
+        let elements = self
+            .elements
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(" \\\\ ");
+        format!("\\begin{{pmatrix}} {} \\end{{pmatrix}}", elements)
+    }
+}
+
+impl Matrix {
+    pub fn to_latex(&self) -> String {
+        let (_, m) = self.dimensions;
+        let rows = self
+            .elements
+            .chunks(m)
+            .map(|row| {
+                row.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" & ")
+            })
+            .collect::<Vec<_>>()
+            .join(" \\\\ ");
+        format!("\\begin{{pmatrix}} {} \\end{{pmatrix}}", rows)
+    }
+}
+
+// LATEX end
+
+// RUST begin
+
+impl Scalar {
+    pub fn to_rust(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Vector {
+    pub fn to_rust(&self) -> String {
+        format!("vec!{:?}", self.elements)
+    }
+}
+
+impl Matrix {
+    pub fn to_rust(&self) -> String {
+        let (_, m) = self.dimensions;
+        let rows = self
+            .elements
+            .chunks(m)
+            .map(|row| format!("vec!{:?}", row))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("vec![{}]", rows)
+    }
+}
+
+// RUST end
+
+// CAS begin
+
+fn cas_list(elements: &[Int], syntax: CasSyntax) -> String {
+    let elements = elements
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match syntax {
+        CasSyntax::SymPy | CasSyntax::Maxima => format!("[{}]", elements),
+        CasSyntax::Mathematica => format!("{{{}}}", elements),
+    }
+}
+
+impl Scalar {
+    pub fn to_cas(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Vector {
+    pub fn to_cas(&self, syntax: CasSyntax) -> String {
+        match syntax {
+            CasSyntax::SymPy => format!("Matrix({})", cas_list(&self.elements, syntax)),
+            CasSyntax::Maxima => {
+                let rows = self
+                    .elements
+                    .iter()
+                    .map(|x| format!("[{}]", x))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("matrix({})", rows)
+            }
+            CasSyntax::Mathematica => cas_list(&self.elements, syntax),
+        }
+    }
+}
+
+impl Matrix {
+    pub fn to_cas(&self, syntax: CasSyntax) -> String {
+        let (_, m) = self.dimensions;
+        let rows = self
+            .elements
+            .chunks(m)
+            .map(|row| cas_list(row, syntax))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match syntax {
+            CasSyntax::SymPy => format!("Matrix([{}])", rows),
+            CasSyntax::Maxima => format!("matrix({})", rows),
+            CasSyntax::Mathematica => format!("{{{}}}", rows),
+        }
+    }
+}
+
+// CAS end
+
+// JSON begin
+
+#[cfg(feature = "json")]
+impl Scalar {
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::from(self.0)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Vector {
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::from(self.elements.clone())
+    }
+}
+
+#[cfg(feature = "json")]
+impl Matrix {
+    pub fn to_json(&self) -> JsonValue {
+        let (_, m) = self.dimensions;
+        JsonValue::Array(
+            self.elements
+                .chunks(m)
+                .map(|row| JsonValue::from(row.to_vec()))
+                .collect(),
+        )
+    }
+}
+
+// JSON end
+
+// MAP begin
+
+impl Scalar {
+    pub fn map(&self, f: impl Fn(Int) -> Int) -> Self {
+        Scalar(f(self.0))
+    }
+}
+
+impl Vector {
+    pub fn map(&self, f: impl Fn(Int) -> Int) -> Self {
+        Vector::new(self.elements.iter().copied().map(f).collect())
+    }
+}
+
+impl Matrix {
+    pub fn map(&self, f: impl Fn(Int) -> Int) -> Self {
+        Matrix {
+            elements: self.elements.iter().copied().map(f).collect(),
+            dimensions: self.dimensions,
+        }
+    }
+}
+
+// MAP end
@@ -3,9 +3,10 @@ use std::{
     ops::{Add, Mul, Neg},
 };
 
-use poly_gnom::traits::{MulTraits, PolyTypes};
+use poly_gnom::traits::{MulTraits, PolyTypes, PowerStrategy};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Types {
     Scalar,
     Vector,
@@ -65,4 +66,12 @@ impl Display for Types {
     }
 }
 
-impl PolyTypes<Types> for Types {}
+impl PolyTypes<Types> for Types {
+    fn power_strategy(&self) -> PowerStrategy {
+        match self {
+            Types::Scalar => PowerStrategy::Custom,
+            Types::Vector => PowerStrategy::Binary,
+            Types::Matrix => PowerStrategy::AdditionChain,
+        }
+    }
+}
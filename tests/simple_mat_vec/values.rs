@@ -6,14 +6,17 @@ use std::{
     ops::{Add, Mul, Neg},
 };
 
-use poly_gnom::traits::{One, PolyValues, Zero};
+#[cfg(feature = "json")]
+use poly_gnom::json::JsonValue;
+use poly_gnom::traits::{CasSyntax, DivideByNatural, One, PolyValues, Zero};
 
 use super::{
     objects::{Int, Matrix, Scalar, Vector},
     types::Types,
 };
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Values {
     Scalar(Scalar),
     Vector(Vector),
@@ -32,6 +35,76 @@ impl Values {
     pub fn new_matrix(elements: Vec<Vec<Int>>) -> Self {
         Values::Matrix(Matrix::new(elements))
     }
+
+    // Reads value printed with Display from the start of input,
+    // returns it together with the number of consumed bytes.
+    pub fn parse(input: &str) -> Option<(Values, usize)> {
+        let (kind, rest) = ["scalar", "vector", "matrix"]
+            .into_iter()
+            .find_map(|kind| input.strip_prefix(kind).map(|rest| (kind, rest)))?;
+        let rest = rest.trim_start().strip_prefix('{')?;
+        let end = rest.find('}')?;
+        let consumed = input.len() - rest.len() + end + 1;
+        let body = rest[..end].trim();
+
+        let elements = |row: &str, separator: char| {
+            row.trim()
+                .strip_prefix('[')?
+                .strip_suffix(']')?
+                .split(separator)
+                .map(|x| x.trim().parse::<Int>().ok())
+                .collect::<Option<Vec<_>>>()
+        };
+        let value = match kind {
+            "scalar" => Values::new_scalar(body.parse().ok()?),
+            "vector" => Values::new_vector(elements(body, ';')?),
+            _ => Values::new_matrix(
+                body.strip_prefix('[')?
+                    .strip_suffix(']')?
+                    .split(';')
+                    .map(|row| elements(row, ','))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+        };
+        Some((value, consumed))
+    }
+
+    // Applies `f` to every element (for example to reduce them modulo some number).
+    pub fn map_elements(&self, f: impl Fn(Int) -> Int) -> Values {
+        match self {
+            Values::Scalar(scalar) => Values::Scalar(scalar.map(f)),
+            Values::Vector(vector) => Values::Vector(vector.map(f)),
+            Values::Matrix(matrix) => Values::Matrix(matrix.map(f)),
+        }
+    }
+
+    // Scalars are numbers, vectors are arrays of numbers and matrices are arrays of rows.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            Values::Scalar(scalar) => scalar.to_json(),
+            Values::Vector(vector) => vector.to_json(),
+            Values::Matrix(matrix) => matrix.to_json(),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &JsonValue) -> Option<Values> {
+        let elements = |json: &JsonValue| {
+            json.as_array()?
+                .iter()
+                .map(JsonValue::as_i64)
+                .collect::<Option<Vec<Int>>>()
+        };
+        match json {
+            JsonValue::Number(number) => Some(Values::new_scalar(number.as_i64()?)),
+            JsonValue::Array(items) if items.iter().all(|item| item.as_array().is_some()) => Some(
+                Values::new_matrix(items.iter().map(elements).collect::<Option<Vec<_>>>()?),
+            ),
+            JsonValue::Array(_) => Some(Values::new_vector(elements(json)?)),
+            _ => None,
+        }
+    }
 }
 
 impl Add for Values {
@@ -122,4 +195,50 @@ impl PolyValues<Types, Values> for Values {
             Values::Matrix(_) => Types::Matrix,
         }
     }
+
+    fn to_latex(&self) -> String {
+        match self {
+            Values::Scalar(scalar) => scalar.to_latex(),
+            Values::Vector(vector) => vector.to_latex(),
+            Values::Matrix(matrix) => matrix.to_latex(),
+        }
+    }
+
+    fn to_cas(&self, syntax: CasSyntax) -> String {
+        match self {
+            Values::Scalar(scalar) => scalar.to_cas(),
+            Values::Vector(vector) => vector.to_cas(syntax),
+            Values::Matrix(matrix) => matrix.to_cas(syntax),
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        match self {
+            Values::Scalar(scalar) => scalar.is_one(),
+            _ => false,
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Values::Scalar(scalar) => scalar.is_negative(),
+            _ => false,
+        }
+    }
+
+    fn pow(self, power: u64) -> Option<Self> {
+        match self {
+            Values::Scalar(scalar) => Some(Values::Scalar(scalar.pow(power))),
+            _ => None,
+        }
+    }
+}
+
+// Elements are integers, so only exact division is supported.
+impl DivideByNatural for Values {
+    fn divide_by_natural(&self, divisor: u64) -> Option<Self> {
+        let divisor = Int::try_from(divisor).ok()?;
+        let quotient = self.map_elements(|element| element / divisor);
+        (quotient.map_elements(|element| element * divisor) == *self).then_some(quotient)
+    }
 }
@@ -1,10 +1,27 @@
 mod simple_mat_vec;
 
 mod tests {
-    use crate::simple_mat_vec::{types::Types, values::Values};
+    use std::collections::HashSet;
+
+    use crate::simple_mat_vec::{
+        codegen::{CCodegen, RustCodegen},
+        generated,
+        types::Types,
+        values::Values,
+    };
     use poly_gnom::{
+        error::{
+            CodegenError, CompositionError, FinalizeError, IdentityTestError, IntegrationError,
+            MapError, PolynomialMapError,
+        },
+        factor::{Factor, SubPoly, Value, Variable},
+        format::{PolyFormatter, PolyParser},
         polynomial::Polynomial,
+        polynomial_map::PolynomialMap,
         symbol::{SymbolInfo, SymbolsProvider},
+        term::{MonomialFactor, Term},
+        traits::{GrLex, GrevLex, Lex, Sampler},
+        visit::PolyVisitor,
     };
 
     fn basic_sumbols_provider() -> SymbolsProvider<Types> {
@@ -154,7 +171,7 @@ mod tests {
             (
                 Values::new_matrix(vec![vec![0, 1], vec![2, 3]]),
                 Values::new_matrix(vec![vec![8, 7], vec![6, 5]]),
-                Some(Values::new_matrix(vec![vec![6, 5], vec![34, 36]])),
+                Some(Values::new_matrix(vec![vec![6, 5], vec![34, 29]])),
             ),
         ];
 
@@ -217,4 +234,1785 @@ mod tests {
             assert_eq!(res, result.ok());
         }
     }
+    #[test]
+    fn batch_evaluation_test() {
+        let provider = basic_sumbols_provider();
+
+        let permutation_matrix = vec![vec![0, 1], vec![1, 0]];
+
+        let inner = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .value(Values::new_matrix(permutation_matrix), 3)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(inner, 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let symbols = vec![
+            provider.get("v").unwrap(),
+            provider.get("x").unwrap(),
+            provider.get("u").unwrap(),
+        ];
+        let bindings = vec![
+            vec![
+                Values::new_vector(vec![1, 2]),
+                Values::new_scalar(3),
+                Values::new_vector(vec![1, 1]),
+            ],
+            vec![
+                Values::new_vector(vec![5, 0]),
+                Values::new_scalar(-1),
+                Values::new_vector(vec![0, 2]),
+            ],
+            vec![
+                Values::new_vector(vec![1, 2]),
+                Values::new_vector(vec![3, 3]),
+                Values::new_vector(vec![1, 1]),
+            ],
+            vec![
+                Values::new_vector(vec![1, 2, 3]),
+                Values::new_scalar(1),
+                Values::new_vector(vec![1, 1]),
+            ],
+        ];
+
+        let results = poly
+            .evaluate_batch(&symbols, bindings.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].clone().ok(),
+            Some(Values::new_vector(vec![7, 5]))
+        );
+        assert_eq!(
+            results[1].clone().ok(),
+            Some(Values::new_vector(vec![0, 8]))
+        );
+        assert!(results[2].is_err());
+        assert!(results[3].is_err());
+
+        for (binding, result) in bindings.into_iter().zip(results) {
+            let expected = symbols
+                .iter()
+                .cloned()
+                .zip(binding)
+                .fold(poly.clone(), |poly, (symbol, value)| {
+                    poly.substitute_value(symbol, value)
+                })
+                .as_value();
+            assert_eq!(expected.ok(), result.ok());
+        }
+
+        let results = poly
+            .evaluate_batch(
+                &symbols,
+                vec![
+                    vec![Values::new_vector(vec![1, 2]), Values::new_scalar(3)],
+                    vec![
+                        Values::new_vector(vec![1, 2]),
+                        Values::new_scalar(3),
+                        Values::new_vector(vec![1, 1]),
+                    ],
+                ],
+            )
+            .collect::<Vec<_>>();
+        assert!(matches!(results[0], Err(FinalizeError::MismatchedBinding)));
+        assert_eq!(
+            results[1].clone().ok(),
+            Some(Values::new_vector(vec![7, 5]))
+        );
+
+        // zero powers of bound symbols, with and without their types known up front
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("x").unwrap(), 0)
+            .variable(provider.get("r").unwrap(), 2)
+            .build()
+            .build()
+            .unwrap();
+        let symbols = vec![provider.get("r").unwrap(), provider.get("x").unwrap()];
+        let results = poly
+            .evaluate_batch(
+                &symbols,
+                vec![
+                    vec![Values::new_scalar(2), Values::new_scalar(5)],
+                    vec![Values::new_vector(vec![1, 2]), Values::new_scalar(5)],
+                ],
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(results[0].clone().ok(), Some(Values::new_scalar(12)));
+        assert!(results[1].is_err());
+    }
+    #[test]
+    fn type_after_substitution_test() {
+        let provider = basic_sumbols_provider();
+
+        let mut poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("r").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+        for _ in 0..32 {
+            poly = MatVecPolynomial::builder()
+                .term_builder(Values::new_scalar(2))
+                .polynomial(poly, 1)
+                .build()
+                .term_builder(Values::new_scalar(1))
+                .variable(provider.get("A").unwrap(), 1)
+                .build()
+                .build()
+                .unwrap();
+        }
+
+        assert!(poly.as_type().is_err());
+        assert!(format!("{:?}", poly).ends_with("| None )"));
+
+        let poly = poly.substitute_variable(provider.get("r").unwrap(), provider.get("B").unwrap());
+        assert_eq!(poly.as_type().unwrap(), Types::Matrix);
+        assert!(format!("{:?}", poly).ends_with("| Matrix )"));
+
+        let poly = poly.substitute_value(provider.get("B").unwrap(), Values::new_scalar(1));
+        assert!(poly.as_type().is_err());
+    }
+
+    #[test]
+    fn power_strategies_test() {
+        let provider = basic_sumbols_provider();
+
+        let power_of = |symbol: &str, power: u64| {
+            MatVecPolynomial::builder()
+                .term_builder(Values::new_scalar(1))
+                .variable(provider.get(symbol).unwrap(), power)
+                .build()
+                .build()
+                .unwrap()
+        };
+
+        let scalar = power_of("x", 13)
+            .substitute_value(provider.get("x").unwrap(), Values::new_scalar(-2))
+            .as_value();
+        assert_eq!(scalar.ok(), Some(Values::new_scalar(-8192)));
+
+        let fibonacci = Values::new_matrix(vec![vec![1, 1], vec![1, 0]]);
+        let mut expected = fibonacci.clone();
+        for power in 1..=90 {
+            let matrix = power_of("A", power)
+                .substitute_value(provider.get("A").unwrap(), fibonacci.clone())
+                .as_value();
+            assert_eq!(matrix.ok(), Some(expected.clone()));
+            expected = (expected * fibonacci.clone()).unwrap();
+        }
+
+        let shear = Values::new_matrix(vec![vec![1, 1], vec![0, 1]]);
+        for power in [127, 128, 129, 1000] {
+            let matrix = power_of("A", power)
+                .substitute_value(provider.get("A").unwrap(), shear.clone())
+                .as_value();
+            assert_eq!(
+                matrix.ok(),
+                Some(Values::new_matrix(vec![vec![1, power as i64], vec![0, 1]]))
+            );
+        }
+
+        let non_square = power_of("A", 2)
+            .substitute_value(
+                provider.get("A").unwrap(),
+                Values::new_matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]),
+            )
+            .as_value();
+        assert!(non_square.is_err());
+
+        assert!(power_of("v", 2).as_type().is_err());
+    }
+
+    #[test]
+    fn fold_constants_test() {
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("y").unwrap(), 2)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("B").unwrap(), 1)
+            .variable(provider.get("z").unwrap(), 1)
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(sum, 1)
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = poly
+            .substitute_value(
+                provider.get("A").unwrap(),
+                Values::new_matrix(vec![vec![1, 1], vec![0, 1]]),
+            )
+            .substitute_value(
+                provider.get("B").unwrap(),
+                Values::new_matrix(vec![vec![1, 0], vec![1, 1]]),
+            )
+            .substitute_value(provider.get("x").unwrap(), Values::new_scalar(3))
+            .substitute_value(provider.get("y").unwrap(), Values::new_scalar(2));
+
+        let folded = poly.clone().fold_constants();
+        assert_eq!(
+            folded.to_string(),
+            "scalar { 2 } matrix { [[2, 1]; [1, 1]] } z matrix { [[1, 1]; [0, 1]] } u + scalar { 1 } scalar { 7 } u"
+        );
+
+        let (z, u) = (provider.get("z").unwrap(), provider.get("u").unwrap());
+        let u_value = Values::new_vector(vec![1, -1]);
+        let evaluate = |poly: MatVecPolynomial, z_value: Values| {
+            poly.substitute_value(z.clone(), z_value)
+                .substitute_value(u.clone(), u_value.clone())
+                .as_value()
+        };
+        assert!(evaluate(poly.clone(), Values::new_vector(vec![1, 2])).is_err());
+        assert!(evaluate(folded.clone(), Values::new_vector(vec![1, 2])).is_err());
+
+        let fully_folded = folded
+            .substitute_value(z.clone(), Values::new_scalar(2))
+            .substitute_value(u.clone(), u_value.clone())
+            .fold_constants();
+        assert_eq!(fully_folded.to_string(), "vector { [3; -11] }");
+        assert_eq!(
+            fully_folded.as_value().ok(),
+            evaluate(poly, Values::new_scalar(2)).ok()
+        );
+    }
+
+    #[test]
+    fn latex_test() {
+        let provider = basic_sumbols_provider();
+        let v =
+            provider.add(SymbolInfo::new_typed("bold_v", Types::Vector).with_latex("\\mathbf{v}"));
+        assert_eq!(v.latex(), Some("\\mathbf{v}"));
+        assert_eq!(provider.get("u").unwrap().latex(), None);
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .variable(provider.get("A").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("B").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(5))
+            .polynomial(sum, 1)
+            .variable(v, 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("y").unwrap(), 2)
+            .variable(provider.get("w").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            poly.to_latex(),
+            "5 \\left( 2 A + B \\right) \\mathbf{v} + 3 u + y^{2} w"
+        );
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .value(Values::new_matrix(vec![vec![0, 1], vec![1, 0]]), 2)
+            .variable(provider.get("x").unwrap(), 3)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .value(Values::new_scalar(3), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            poly.to_latex(),
+            "{\\begin{pmatrix} 0 & 1 \\\\ 1 & 0 \\end{pmatrix}}^{2} x^{3} + 2 \\cdot 3 + 1"
+        );
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(-1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(-3))
+            .variable(provider.get("y").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(poly.to_latex(), "-x - 3 y + 2");
+    }
+
+    #[test]
+    fn formatter_test() {
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .variable(provider.get("A").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(-1))
+            .variable(provider.get("B").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+        let single = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(5))
+            .polynomial(sum, 2)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(-3))
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(single, 1)
+            .variable(provider.get("y").unwrap(), 12)
+            .variable(provider.get("w").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(PolyFormatter::new().format(&poly), poly.to_string());
+
+        let formatter = PolyFormatter::new()
+            .hide_unit_coefficients(true)
+            .negative_as_subtraction(true)
+            .superscript_powers(true)
+            .explicit_multiplication(true)
+            .minimal_parentheses(true);
+        assert_eq!(
+            formatter.format(&poly),
+            "scalar { 5 } * ( scalar { 2 } * A - B )² * v - scalar { 3 } * u + scalar { 3 } * x * y¹² * w"
+        );
+
+        let wrapped = formatter.clone().line_width(Some(46)).format(&poly);
+        assert_eq!(
+            wrapped,
+            "scalar { 5 } * ( scalar { 2 } * A - B )² * v\n- scalar { 3 } * u\n+ scalar { 3 } * x * y¹² * w"
+        );
+        assert!(wrapped.lines().all(|line| line.chars().count() <= 46));
+
+        let evaluate = |poly: MatVecPolynomial| {
+            poly.substitute_value(
+                provider.get("A").unwrap(),
+                Values::new_matrix(vec![vec![1, 2], vec![3, 4]]),
+            )
+            .substitute_value(
+                provider.get("B").unwrap(),
+                Values::new_matrix(vec![vec![0, 1], vec![1, 0]]),
+            )
+            .substitute_value(provider.get("v").unwrap(), Values::new_vector(vec![1, -1]))
+            .substitute_value(provider.get("u").unwrap(), Values::new_vector(vec![2, 3]))
+            .substitute_value(provider.get("w").unwrap(), Values::new_vector(vec![1, 0]))
+            .substitute_value(provider.get("x").unwrap(), Values::new_scalar(2))
+            .substitute_value(provider.get("y").unwrap(), Values::new_scalar(-1))
+            .as_value()
+            .unwrap()
+        };
+        let expected = evaluate(poly.clone());
+
+        let parser = PolyParser::new(&provider, Values::parse).unit_type(Types::Scalar);
+        for text in [
+            poly.to_string(),
+            formatter.format(&poly),
+            wrapped,
+            PolyFormatter::new().superscript_powers(true).format(&poly),
+        ] {
+            let parsed: MatVecPolynomial = parser.parse(&text).unwrap();
+            assert_eq!(evaluate(parsed), expected);
+        }
+
+        let parsed: MatVecPolynomial = PolyParser::new(&provider, Values::parse)
+            .parse(&poly.to_string())
+            .unwrap();
+        assert_eq!(parsed.to_string(), poly.to_string());
+
+        assert!(PolyParser::new(&provider, Values::parse)
+            .parse::<Values>(&formatter.format(&poly))
+            .is_err());
+        assert!(parser.parse::<Values>("x + ( y").is_err());
+        assert!(parser.parse::<Values>("x + unknown").is_err());
+    }
+
+    #[test]
+    fn dot_test() {
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .variable(provider.get("A").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("B").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(5))
+            .polynomial(sum, 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(provider.get("r").unwrap(), 1)
+            .variable(provider.get("w").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let dot = poly.to_dot();
+        assert!(dot.starts_with("digraph polynomial {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("[label=").count(), 20);
+        assert_eq!(dot.matches(" -> ").count(), 19);
+        assert!(dot.contains("    n0 [label=\"SubPoly\\ntype error: NoTypeToFinalize\"];\n"));
+        assert!(dot.contains(
+            "    n1 [label=\"Term\\ncoefficient: scalar { 5 }\\ntype: Vector\"];\n    n0 -> n1;\n"
+        ));
+        assert!(dot.contains(
+            "    n2 [label=\"MonomialFactor\\npower: 1\\ntype: Matrix\"];\n    n1 -> n2;\n"
+        ));
+        assert!(dot.contains("[label=\"Variable\\nsymbol: r\\ntype error: NoTypeToFinalize\"];"));
+        assert!(dot.contains("[label=\"Variable\\nsymbol: B\\ntype: Matrix\"];"));
+    }
+
+    #[test]
+    fn traversal_test() {
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("y").unwrap(), 3)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .polynomial(sum, 2)
+            .value(Values::new_scalar(5), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .build()
+            .build()
+            .unwrap();
+
+        let terms = poly.terms();
+        assert_eq!(terms.len(), 2);
+        assert_eq!(*terms[0].coefficient(), Values::new_scalar(2));
+        assert!(terms[1].is_constant());
+        let factors = terms[0].factors();
+        assert_eq!(factors[0].power(), 2);
+        match factors[0].factor() {
+            Factor::SubPoly(sub_poly) => {
+                let y_factor = &sub_poly.terms()[1].factors()[0];
+                assert_eq!(y_factor.power(), 3);
+                assert!(
+                    matches!(y_factor.factor(), Factor::Variable(variable) if variable.symbol().label == "y")
+                );
+            }
+            _ => panic!("expected sub-polynomial"),
+        }
+        assert!(
+            matches!(factors[1].factor(), Factor::Value(value) if *value.value() == Values::new_scalar(5))
+        );
+
+        #[derive(Default)]
+        struct Trace {
+            events: Vec<String>,
+            depth: usize,
+            max_depth: usize,
+        }
+
+        impl PolyVisitor<Values, Types> for Trace {
+            fn pre_sub_poly(&mut self, _: &SubPoly<Values, Types>) {
+                self.depth += 1;
+                self.max_depth = self.max_depth.max(self.depth);
+                self.events.push("(".to_owned());
+            }
+
+            fn post_sub_poly(&mut self, _: &SubPoly<Values, Types>) {
+                self.depth -= 1;
+                self.events.push(")".to_owned());
+            }
+
+            fn pre_term(&mut self, term: &Term<Values, Types>) {
+                self.events.push(format!("term {}", term.coefficient()));
+            }
+
+            fn post_monomial_factor(&mut self, factor: &MonomialFactor<Values, Types>) {
+                self.events.push(format!("^{}", factor.power()));
+            }
+
+            fn pre_value(&mut self, value: &Value<Values, Types>) {
+                self.events.push(value.value().to_string());
+            }
+
+            fn pre_variable(&mut self, variable: &Variable<Types>) {
+                self.events.push(variable.symbol().label.clone());
+            }
+        }
+
+        let mut trace = Trace::default();
+        poly.visit(&mut trace);
+        assert_eq!(
+            trace.events.join(" "),
+            "( term scalar { 2 } ( term scalar { 1 } x ^1 term scalar { 1 } y ^3 ) ^2 \
+             scalar { 5 } ^1 term scalar { 3 } )"
+        );
+        assert_eq!(trace.max_depth, 2);
+    }
+
+    #[test]
+    fn queries_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(y.clone(), 3)
+            .build()
+            .build()
+            .unwrap();
+
+        // 2 (x + y^3)^2 x 5 + 3 + r u^0
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .polynomial(sum, 2)
+            .variable(x.clone(), 1)
+            .value(Values::new_scalar(5), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("r").unwrap(), 1)
+            .variable(provider.get("u").unwrap(), 0)
+            .build()
+            .build()
+            .unwrap();
+
+        let labels = poly
+            .free_symbols()
+            .into_iter()
+            .map(|symbol| symbol.label.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["x", "y", "u", "r"]);
+        assert!(poly.contains(&provider.get("u").unwrap()));
+        assert!(!poly.contains(&provider.get("z").unwrap()));
+
+        assert_eq!(poly.degree_in(&x), 3);
+        assert_eq!(poly.degree_in(&y), 6);
+        assert_eq!(poly.degree_in(&provider.get("z").unwrap()), 0);
+        assert_eq!(poly.total_degree(), 7);
+
+        assert_eq!(poly.term_count(), 3);
+        assert_eq!(poly.depth(), 2);
+        assert_eq!(poly.node_count(), 20);
+        assert_eq!(poly.to_dot().matches("[label=").count(), poly.node_count());
+
+        let bound = poly
+            .substitute_value(x, Values::new_scalar(1))
+            .substitute_value(y, Values::new_scalar(1));
+        assert_eq!(bound.free_symbols().len(), 2);
+        assert_eq!(bound.total_degree(), 1);
+
+        // (z^MAX)^2 y does not overflow
+        let huge = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("z").unwrap(), u64::MAX)
+            .build()
+            .build()
+            .unwrap();
+        let huge = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(huge, 2)
+            .variable(provider.get("y").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(huge.total_degree(), u64::MAX);
+    }
+
+    #[test]
+    fn map_domain_test() {
+        let provider = basic_sumbols_provider();
+        let modulo = |x: i64| x.rem_euclid(5);
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(7))
+            .variable(provider.get("x").unwrap(), 2)
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(-3))
+            .value(Values::new_vector(vec![4, 9]), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let target = SymbolsProvider::empty();
+        target.add(SymbolInfo::new_typed("A", Types::Matrix));
+        let reduced: MatVecPolynomial = poly
+            .map_domain(|value| value.map_elements(modulo), Types::clone, &target)
+            .unwrap();
+        assert_eq!(target.symbols().len(), 3);
+        assert_eq!(
+            target.get("v").unwrap().associated_type,
+            Some(Types::Vector)
+        );
+
+        let evaluate = |poly: MatVecPolynomial, provider: &SymbolsProvider<Types>| {
+            poly.substitute_value(provider.get("x").unwrap(), Values::new_scalar(3))
+                .substitute_value(
+                    provider.get("A").unwrap(),
+                    Values::new_matrix(vec![vec![1, 2], vec![3, 4]]),
+                )
+                .substitute_value(provider.get("v").unwrap(), Values::new_vector(vec![1, 1]))
+                .as_value()
+                .unwrap()
+                .map_elements(modulo)
+        };
+        assert_eq!(
+            evaluate(reduced, &target),
+            evaluate(poly.clone(), &provider)
+        );
+
+        // types are checked against the mapped ones
+        let everything_scalar = |_: &Types| Types::Scalar;
+        assert_eq!(
+            poly.map_domain(Values::clone, everything_scalar, &SymbolsProvider::empty())
+                .err(),
+            Some(MapError::ValueTypeMismatch)
+        );
+        let conflicting = SymbolsProvider::empty();
+        conflicting.add(SymbolInfo::new_typed("v", Types::Scalar));
+        assert_eq!(
+            poly.map_domain(Values::clone, Types::clone, &conflicting)
+                .err(),
+            Some(MapError::SymbolTypeMismatch("v".to_owned()))
+        );
+    }
+
+    #[test]
+    fn derivative_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(y.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        // 2·x^3·y + (x + y)^2 + 5
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .variable(x.clone(), 3)
+            .variable(y.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(sum, 2)
+            .build()
+            .term_builder(Values::new_scalar(5))
+            .build()
+            .build()
+            .unwrap();
+
+        // 6·x^2·y + 2·(x + y)
+        let derivative = poly.derivative(&x).unwrap();
+        assert_eq!(derivative.term_count(), 2);
+        let evaluate = |poly: MatVecPolynomial| {
+            poly.substitute_value(x.clone(), Values::new_scalar(2))
+                .substitute_value(y.clone(), Values::new_scalar(5))
+                .as_value()
+                .unwrap()
+        };
+        assert_eq!(evaluate(derivative), Values::new_scalar(134));
+        assert_eq!(
+            evaluate(poly.derivative(&provider.get("z").unwrap()).unwrap()),
+            Values::new_scalar(0)
+        );
+        assert!(matches!(
+            poly.derivative(&provider.get("r").unwrap()),
+            Err(FinalizeError::NoTypeToFinalize)
+        ));
+
+        // d(A^2·v) = dA·A·v + A·dA·v, factor order is kept for matrices
+        let a = provider.get("A").unwrap();
+        let b = provider.get("B").unwrap();
+        let v = provider.get("v").unwrap();
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(a.clone(), 2)
+            .variable(v.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            poly.derivative(&a),
+            Err(FinalizeError::NoValueToFinalize)
+        ));
+        let derivative = poly.directional_derivative(&a, &b);
+        assert_eq!(derivative.term_count(), 2);
+        assert_eq!(derivative.free_symbols().len(), 3);
+        let value = derivative
+            .substitute_value(a, Values::new_matrix(vec![vec![1, 2], vec![3, 4]]))
+            .substitute_value(b, Values::new_matrix(vec![vec![0, 1], vec![1, 0]]))
+            .substitute_value(v, Values::new_vector(vec![1, 1]))
+            .as_value()
+            .unwrap();
+        // B·A·v = (7, 3), A·B·v = (3, 7)
+        assert_eq!(value, Values::new_vector(vec![10, 10]));
+    }
+
+    #[test]
+    fn polynomial_map_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let variables = [x.clone(), y.clone()];
+
+        // f(x, y) = (x^2·y, x + 3·y)
+        let f = PolynomialMap::new(
+            &provider,
+            vec![
+                MatVecPolynomial::builder()
+                    .term_builder(Values::new_scalar(1))
+                    .variable(x.clone(), 2)
+                    .variable(y.clone(), 1)
+                    .build()
+                    .build()
+                    .unwrap(),
+                MatVecPolynomial::builder()
+                    .term_builder(Values::new_scalar(1))
+                    .variable(x.clone(), 1)
+                    .build()
+                    .term_builder(Values::new_scalar(3))
+                    .variable(y.clone(), 1)
+                    .build()
+                    .build()
+                    .unwrap(),
+            ],
+        )
+        .unwrap();
+        // g(x, y) = (x + y, x·y)
+        let g = PolynomialMap::new(
+            &provider,
+            vec![
+                MatVecPolynomial::builder()
+                    .term_builder(Values::new_scalar(1))
+                    .variable(x.clone(), 1)
+                    .build()
+                    .term_builder(Values::new_scalar(1))
+                    .variable(y.clone(), 1)
+                    .build()
+                    .build()
+                    .unwrap(),
+                MatVecPolynomial::builder()
+                    .term_builder(Values::new_scalar(1))
+                    .variable(x.clone(), 1)
+                    .variable(y.clone(), 1)
+                    .build()
+                    .build()
+                    .unwrap(),
+            ],
+        )
+        .unwrap();
+        let evaluate = |poly: &MatVecPolynomial, x_value: i64, y_value: i64| {
+            poly.clone()
+                .substitute_value(x.clone(), Values::new_scalar(x_value))
+                .substitute_value(y.clone(), Values::new_scalar(y_value))
+                .as_value()
+                .unwrap()
+        };
+
+        let jacobian = f.jacobian(&variables).unwrap();
+        let values = jacobian
+            .iter()
+            .map(|row| row.iter().map(|poly| evaluate(poly, 2, 5)).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert_eq!(
+            values,
+            vec![
+                vec![Values::new_scalar(20), Values::new_scalar(4)],
+                vec![Values::new_scalar(1), Values::new_scalar(3)],
+            ]
+        );
+        let gradient = f.components()[0].gradient(&variables).unwrap();
+        assert_eq!(
+            gradient
+                .iter()
+                .map(|poly| evaluate(poly, 2, 5))
+                .collect::<Vec<_>>(),
+            vec![Values::new_scalar(20), Values::new_scalar(4)]
+        );
+
+        // f(g(1, 2)) = f(3, 2) = (18, 9), symbols are replaced simultaneously
+        let composition = f.compose(&variables, &g).unwrap();
+        assert_eq!(composition.len(), 2);
+        assert_eq!(
+            composition
+                .components()
+                .iter()
+                .map(|poly| evaluate(poly, 1, 2))
+                .collect::<Vec<_>>(),
+            vec![Values::new_scalar(18), Values::new_scalar(9)]
+        );
+
+        assert_eq!(
+            f.compose(&variables[..1], &g).err(),
+            Some(CompositionError::ArityMismatch(1, 2))
+        );
+        let vectors = PolynomialMap::new(
+            &provider,
+            vec![MatVecPolynomial::builder()
+                .term_builder(Values::new_scalar(1))
+                .variable(provider.get("v").unwrap(), 1)
+                .build()
+                .build()
+                .unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            f.compose(&variables[..1], &vectors).err(),
+            Some(CompositionError::TypeMismatch("x".to_owned()))
+        );
+
+        // symbols with the same labels from another provider are not the same symbols
+        let foreign = basic_sumbols_provider();
+        assert_eq!(
+            PolynomialMap::new(&foreign, f.components().to_vec()).err(),
+            Some(PolynomialMapError::ForeignSymbol("x".to_owned()))
+        );
+        let foreign_inner = PolynomialMap::new(
+            &foreign,
+            vec![MatVecPolynomial::builder()
+                .term_builder(Values::new_scalar(1))
+                .variable(foreign.get("y").unwrap(), 1)
+                .build()
+                .build()
+                .unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            f.compose(&variables[..1], &foreign_inner).err(),
+            Some(CompositionError::ForeignSymbol("y".to_owned()))
+        );
+    }
+
+    #[test]
+    fn integrate_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let shifted = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .build()
+            .build()
+            .unwrap();
+        // 3·x^2 + 2·x·y + 3·(x + 1)^2·y^2 + 4
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(3))
+            .variable(x.clone(), 2)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(x.clone(), 1)
+            .variable(y.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .polynomial(shifted, 2)
+            .variable(y.clone(), 2)
+            .build()
+            .term_builder(Values::new_scalar(4))
+            .build()
+            .build()
+            .unwrap();
+
+        // x^3 + x^2·y + (x^3 + 3·x^2 + 3·x)·y^2 + 4·x
+        let antiderivative = poly.integrate(&x).unwrap();
+        assert_eq!(antiderivative.degree_in(&x), 3);
+        let evaluate = |poly: MatVecPolynomial, x_value: i64| {
+            poly.substitute_value(x.clone(), Values::new_scalar(x_value))
+                .substitute_value(y.clone(), Values::new_scalar(5))
+                .as_value()
+                .unwrap()
+        };
+        assert_eq!(
+            evaluate(antiderivative, 2),
+            Values::new_scalar(8 + 20 + 650 + 8)
+        );
+        let definite = poly
+            .integrate_between(&x, Values::new_scalar(1), Values::new_scalar(2))
+            .unwrap();
+        assert_eq!(definite.free_symbols().len(), 1);
+        assert_eq!(
+            evaluate(definite, 0),
+            Values::new_scalar((8 + 20 + 650 + 8) - (1 + 5 + 175 + 4))
+        );
+
+        // integer coefficients are not always divisible
+        let linear = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            linear.integrate(&x),
+            Err(IntegrationError::Indivisible(2))
+        ));
+        assert!(matches!(
+            linear.integrate(&provider.get("A").unwrap()),
+            Err(IntegrationError::NonCommutative(_))
+        ));
+        assert!(matches!(
+            linear.integrate(&provider.get("r").unwrap()),
+            Err(IntegrationError::UntypedSymbol(_))
+        ));
+
+        // powers of the symbol that do not fit into u64
+        let huge = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), u64::MAX)
+            .build()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            huge.integrate(&x),
+            Err(IntegrationError::PowerOverflow)
+        ));
+        let huge = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), u64::MAX - 1)
+            .variable(x.clone(), 2)
+            .build()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            huge.integrate(&x),
+            Err(IntegrationError::PowerOverflow)
+        ));
+    }
+
+    #[test]
+    fn term_order_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let z = provider.get("z").unwrap();
+        let labels = |factors: &[MonomialFactor<Values, Types>]| {
+            factors
+                .iter()
+                .map(|factor| match factor.factor() {
+                    Factor::Variable(variable) => {
+                        format!("{}^{}", variable.symbol().label, factor.power())
+                    }
+                    _ => "_".to_owned(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // 2 + y·x + x^2 + 3·y^2
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(y.clone(), 1)
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 2)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(y.clone(), 2)
+            .build()
+            .build()
+            .unwrap();
+        let sorted = poly.clone().sort_terms(&Lex);
+        assert_eq!(
+            sorted
+                .terms()
+                .iter()
+                .map(|term| labels(term.factors()))
+                .collect::<Vec<_>>(),
+            vec![
+                vec!["x^2".to_owned()],
+                vec!["x^1".to_owned(), "y^1".to_owned()],
+                vec!["y^2".to_owned()],
+                vec![],
+            ]
+        );
+        assert_eq!(poly.leading_coefficient(&Lex), Some(&Values::new_scalar(1)));
+        assert_eq!(labels(poly.leading_monomial(&Lex).unwrap()), vec!["x^2"]);
+        let evaluate = |poly: MatVecPolynomial| {
+            poly.substitute_value(x.clone(), Values::new_scalar(2))
+                .substitute_value(y.clone(), Values::new_scalar(5))
+                .as_value()
+                .unwrap()
+        };
+        assert_eq!(evaluate(sorted), evaluate(poly));
+
+        // x + y^2 and x^2·z + x·y^2 distinguish the orders
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(y.clone(), 2)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(poly.leading_coefficient(&Lex), Some(&Values::new_scalar(1)));
+        assert_eq!(
+            poly.leading_coefficient(&GrLex),
+            Some(&Values::new_scalar(2))
+        );
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 2)
+            .variable(z.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(x.clone(), 1)
+            .variable(y.clone(), 2)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(
+            poly.leading_coefficient(&GrLex),
+            Some(&Values::new_scalar(1))
+        );
+        assert_eq!(
+            poly.leading_coefficient(&GrevLex),
+            Some(&Values::new_scalar(2))
+        );
+
+        // non-commutative words are ordered by degree, then lexicographically, factors are kept in place
+        let a = provider.get("A").unwrap();
+        let b = provider.get("B").unwrap();
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(a.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(b.clone(), 1)
+            .variable(a.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(b.clone(), 1)
+            .variable(a.clone(), 2)
+            .build()
+            .term_builder(Values::new_scalar(4))
+            .variable(a.clone(), 1)
+            .variable(b.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(
+            poly.sort_terms(&GrLex)
+                .terms()
+                .iter()
+                .map(|term| labels(term.factors()).join(" "))
+                .collect::<Vec<_>>(),
+            vec!["B^1 A^2", "A^1 B^1", "B^1 A^1", "A^1"]
+        );
+
+        // powers are compared without expanding them: A^N B < A^N A = A^(N + 1)
+        let huge = 1 << 40;
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(a.clone(), huge)
+            .variable(b.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(a.clone(), huge)
+            .variable(a.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(a.clone(), huge + 1)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(
+            poly.leading_coefficient(&GrLex),
+            Some(&Values::new_scalar(2))
+        );
+        assert_eq!(
+            poly.sort_terms(&GrLex)
+                .terms()
+                .iter()
+                .map(Term::coefficient)
+                .collect::<Vec<_>>(),
+            [
+                &Values::new_scalar(2),
+                &Values::new_scalar(3),
+                &Values::new_scalar(1)
+            ]
+        );
+
+        // symbols of another provider share ids, but they are still different letters
+        let other = basic_sumbols_provider();
+        let other_a = other.get("A").unwrap();
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(other_a.clone(), 1)
+            .variable(b.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(a.clone(), 1)
+            .variable(b.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(
+            poly.sort_terms(&GrLex)
+                .terms()
+                .iter()
+                .map(Term::coefficient)
+                .collect::<Vec<_>>(),
+            [&Values::new_scalar(2), &Values::new_scalar(1)]
+        );
+        let other_x = other.get("x").unwrap();
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(other_x.clone(), 1)
+            .variable(x.clone(), 1)
+            .build()
+            .build()
+            .unwrap();
+        let sorted = poly.sort_terms(&Lex);
+        let factors = sorted.terms()[0].factors();
+        assert!(
+            matches!(factors[0].factor(), Factor::Variable(variable) if *variable.symbol() == x)
+        );
+        assert!(
+            matches!(factors[1].factor(), Factor::Variable(variable) if *variable.symbol() == other_x)
+        );
+    }
+
+    #[test]
+    fn equality_test() {
+        let provider = basic_sumbols_provider();
+        let parser = PolyParser::new(&provider, Values::parse).unit_type(Types::Scalar);
+        let parse = |text: &str| -> MatVecPolynomial { parser.parse(text).unwrap() };
+
+        let poly = parse("x * y + scalar { 2 } * A");
+        assert!(poly == parse("x * y + scalar { 2 } * A"));
+        assert!(poly.structurally_eq(&poly.clone()));
+        assert!(poly != parse("y * x + scalar { 2 } * A"));
+        assert!(poly != parse("scalar { 2 } * A + x * y"));
+        assert!(poly.equivalent(&parse("scalar { 2 } * A + y * x")));
+
+        assert!(parse("( x + y )^2").equivalent(&parse("x^2 + scalar { 2 } * x * y + y^2")));
+        assert!(!parse("( A + B )^2").equivalent(&parse("A^2 + scalar { 2 } * A * B + B^2")));
+        assert!(parse("( A + B )^2").equivalent(&parse("A^2 + A * B + B * A + B^2")));
+        assert!(!parse("A * B").equivalent(&parse("B * A")));
+        assert!(parse("x * A * y * B").equivalent(&parse("A * B * y * x")));
+        assert!(parse("scalar { 2 } * A + scalar { 3 } * A").equivalent(&parse("scalar { 5 } * A")));
+        assert!(parse("x - x").equivalent(&MatVecPolynomial::zero(Types::Scalar).unwrap()));
+        assert!(!parse("x - y").equivalent(&MatVecPolynomial::zero(Types::Scalar).unwrap()));
+        assert!(!parse("r * p").equivalent(&parse("p * r")));
+        // scalar and vector coefficients of x can not be added up, and the counts of terms differ
+        let mixed = parse("vector { [1; 2] } * x + scalar { 2 } * x + scalar { 2 } * x");
+        assert!(mixed.equivalent(&mixed.clone()));
+        assert!(!mixed.equivalent(&parse(
+            "scalar { 2 } * x + vector { [1; 2] } * x + vector { [1; 2] } * x"
+        )));
+
+        let generated = [
+            parse("x * y"),
+            parse("x * y"),
+            parse("y * x"),
+            parse("A * B"),
+            parse("A * B"),
+        ];
+        // cached types are not hashed
+        #[allow(clippy::mutable_key_type)]
+        let unique = generated.iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn probably_equal_test() {
+        // linear congruential generator, so the test is deterministic
+        struct Random(u64);
+
+        impl Random {
+            fn next(&mut self) -> i64 {
+                self.0 = self
+                    .0
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (self.0 >> 33) as i64 % 19 - 9
+            }
+        }
+
+        impl Sampler<Values, Types> for Random {
+            fn sample(&mut self, value_type: &Types) -> Option<Values> {
+                match value_type {
+                    Types::Scalar => Some(Values::new_scalar(self.next())),
+                    Types::Vector => None,
+                    Types::Matrix => Some(Values::new_matrix(vec![
+                        vec![self.next(), self.next()],
+                        vec![self.next(), self.next()],
+                    ])),
+                }
+            }
+        }
+
+        let provider = basic_sumbols_provider();
+        let parser = PolyParser::new(&provider, Values::parse).unit_type(Types::Scalar);
+        let parse = |text: &str| -> MatVecPolynomial { parser.parse(text).unwrap() };
+        let mut sampler = Random(7);
+
+        let square = parse("( A + B )^2");
+        let expanded = parse("A^2 + A * B + B * A + B^2");
+        assert!(square.probably_equal(&expanded, &mut sampler, 10).unwrap());
+        assert!(!square
+            .probably_equal(&parse("A^2 + scalar { 2 } * A * B + B^2"), &mut sampler, 10)
+            .unwrap());
+        assert!(parse("( x + y ) * ( x - y )")
+            .probably_equal(&parse("x^2 - y^2"), &mut sampler, 10)
+            .unwrap());
+        assert!(!parse("x^2")
+            .probably_equal(&parse("x"), &mut sampler, 10)
+            .unwrap());
+        assert!(parse("x^2")
+            .probably_equal(&parse("x"), &mut sampler, 0)
+            .unwrap());
+
+        assert!(matches!(
+            parse("r").probably_equal(&parse("r"), &mut sampler, 1),
+            Err(IdentityTestError::UntypedSymbol(label)) if label == "r"
+        ));
+        assert!(matches!(
+            parse("u").probably_equal(&parse("u"), &mut sampler, 1),
+            Err(IdentityTestError::UnsupportedType(_))
+        ));
+        assert!(matches!(
+            parse("x + A").probably_equal(&parse("A"), &mut sampler, 1),
+            Err(IdentityTestError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn cas_export_test() {
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("y").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .polynomial(sum, 2)
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("A").unwrap(), 2)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_vector(vec![1, 2]))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            poly.to_sympy(),
+            "from sympy import *\n\
+             x = Symbol('x')\n\
+             y = Symbol('y')\n\
+             v = Symbol('v', commutative=False)\n\
+             A = Symbol('A', commutative=False)\n\
+             expr = 2*(x + y)**2*A*v + 3*A**2*v + Matrix([1, 2])*x\n"
+        );
+        assert_eq!(
+            poly.to_maxima(),
+            "(2*(x + y)^2*A) . v + (3*A^^2) . v + matrix([1], [2])*x"
+        );
+        assert_eq!(
+            poly.to_mathematica(),
+            "(2*(x + y)^2*A)**v + (3*(A**A))**v + {1, 2}*x"
+        );
+
+        // A x B must not be read as A (x B), where `*` could swap A and x B
+        let mixed = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("x").unwrap(), 1)
+            .variable(provider.get("B").unwrap(), 1)
+            .variable(provider.get("y").unwrap(), 1)
+            .variable(provider.get("C").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert_eq!(mixed.to_maxima(), "((A*x) . B*y) . C");
+        assert_eq!(mixed.to_mathematica(), "((A*x)**B*y)**C");
+        assert!(mixed.to_sympy().ends_with("expr = A*x*B*y*C\n"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_test() {
+        use poly_gnom::error::JsonError;
+
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_vector(vec![1, -2]))
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .value(Values::new_matrix(vec![vec![1, 2], vec![3, 4]]), 1)
+            .polynomial(sum, 2)
+            .build()
+            .build()
+            .unwrap();
+
+        let json = poly.to_json(Values::to_json);
+        assert_eq!(
+            json,
+            "{\"format\":\"poly-gnom\",\"polynomial\":{\"kind\":\"subpoly\",\"terms\":[\
+             {\"coefficient\":2,\"factors\":[\
+             {\"base\":{\"kind\":\"value\",\"value\":[[1,2],[3,4]]},\"power\":1},\
+             {\"base\":{\"kind\":\"subpoly\",\"terms\":[\
+             {\"coefficient\":1,\"factors\":[{\"base\":{\"kind\":\"variable\",\"symbol\":\"x\"},\"power\":1}]},\
+             {\"coefficient\":[1,-2],\"factors\":[]}]},\"power\":2}]}]},\"version\":1}"
+        );
+
+        let restored = MatVecPolynomial::from_json(&json, &provider, Values::from_json).unwrap();
+        assert_eq!(restored.to_string(), poly.to_string());
+
+        // documents written by other tools may be formatted differently
+        let external = r#"{
+            "version": 1, "format": "poly-gnom",
+            "polynomial": {"kind": "subpoly", "terms": [
+                {"coefficient": 3, "factors": [{"base": {"kind": "variable", "symbol": "y"}, "power": 2}]}
+            ]}
+        }"#;
+        let external = MatVecPolynomial::from_json(external, &provider, Values::from_json)
+            .unwrap()
+            .substitute_value(provider.get("y").unwrap(), Values::new_scalar(2));
+        assert_eq!(external.as_value().unwrap(), Values::new_scalar(12));
+
+        let apply = r#"{"format": "poly-gnom", "version": 1, "polynomial": {"kind": "subpoly", "terms": [
+            {"coefficient": 1, "factors": [{"power": 1, "base": {"kind": "apply", "function": "sin", "arguments": []}}]}
+        ]}}"#;
+        assert_eq!(
+            MatVecPolynomial::from_json(apply, &provider, Values::from_json).err(),
+            Some(JsonError::UnsupportedNode("apply".to_owned()))
+        );
+        let bogus = apply.replace("\"apply\"", "\"integral\"");
+        assert_eq!(
+            MatVecPolynomial::from_json(&bogus, &provider, Values::from_json).err(),
+            Some(JsonError::InvalidDocument("kind".to_owned()))
+        );
+        let unknown = json.replace("\"x\"", "\"t\"");
+        assert_eq!(
+            MatVecPolynomial::from_json(&unknown, &provider, Values::from_json).err(),
+            Some(JsonError::UnknownSymbol("t".to_owned()))
+        );
+        let future = json.replace("\"version\":1", "\"version\":2");
+        assert_eq!(
+            MatVecPolynomial::from_json(&future, &provider, Values::from_json).err(),
+            Some(JsonError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            MatVecPolynomial::from_json(&json[1..], &provider, Values::from_json).err(),
+            Some(JsonError::Syntax { line: 1, column: 9 })
+        );
+        // only strict JSON is accepted
+        for malformed in [
+            json.replace("\"power\":1", "\"power\":01"),
+            json.replace("\"power\":1", "\"power\":1."),
+            json.replace("\"x\"", "\"\\u+abc\""),
+            format!("{}{}", "[".repeat(1000), "]".repeat(1000)),
+        ] {
+            assert!(matches!(
+                MatVecPolynomial::from_json(&malformed, &provider, Values::from_json),
+                Err(JsonError::Syntax { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn rust_codegen_test() {
+        let provider = basic_sumbols_provider();
+        let symbols = |labels: &[&str]| {
+            labels
+                .iter()
+                .map(|label| provider.get(label).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("y").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .polynomial(sum.clone(), 2)
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(1))
+            .polynomial(sum, 2)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let source = poly
+            .to_rust_fn("model", &symbols(&["x", "y", "A", "v", "z"]), &RustCodegen)
+            .unwrap();
+        // (x + y)^2 is computed once, unit coefficients are dropped and `z` is not used
+        assert_eq!(source, include_str!("simple_mat_vec/model.rs"));
+
+        // the same source is compiled into the tests, so it has to agree with `as_value`
+        let bound = [
+            ("x", Values::new_scalar(2)),
+            ("y", Values::new_scalar(3)),
+            ("A", Values::new_matrix(vec![vec![1, 2], vec![3, 4]])),
+            ("v", Values::new_vector(vec![5, -1])),
+        ]
+        .into_iter()
+        .fold(poly.clone(), |poly, (label, value)| {
+            poly.substitute_value(provider.get(label).unwrap(), value)
+        });
+        assert_eq!(
+            Values::new_vector(generated::model(
+                2,
+                3,
+                vec![vec![1, 2], vec![3, 4]],
+                vec![5, -1],
+                0
+            )),
+            bound.as_value().unwrap()
+        );
+
+        assert!(matches!(
+            poly.to_rust_fn("model", &symbols(&["x", "y", "A"]), &RustCodegen),
+            Err(CodegenError::UnboundSymbol(label)) if label == "v"
+        ));
+        assert!(matches!(
+            poly.to_rust_fn("model", &symbols(&["x", "y", "A", "v", "r"]), &RustCodegen),
+            Err(CodegenError::UntypedSymbol(label)) if label == "r"
+        ));
+    }
+
+    #[test]
+    fn c_codegen_test() {
+        let provider = basic_sumbols_provider();
+        let x = provider.get("x").unwrap();
+        let y = provider.get("y").unwrap();
+        let z = provider.get("z").unwrap();
+
+        // x^2 y + 3 x y + 2 y^2 + 5
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 2)
+            .variable(y.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(x.clone(), 1)
+            .variable(y.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(2))
+            .variable(y.clone(), 2)
+            .build()
+            .term_builder(Values::new_scalar(5))
+            .build()
+            .build()
+            .unwrap();
+
+        let c = poly
+            .to_c("model", &[x.clone(), y.clone(), z], &CCodegen)
+            .unwrap();
+        assert_eq!(
+            c.header,
+            r#"#ifndef MODEL_H
+#define MODEL_H
+
+/* Arguments of model:
+ *   0: x
+ *   1: y
+ *   2: z
+ */
+long long model(long long x, long long y, long long z);
+
+#endif
+"#
+        );
+        // x (x y + 3 y) + (2 y^2 + 5)
+        assert_eq!(
+            c.source,
+            r#"long long model(long long x, long long y, long long z)
+{
+    (void)z;
+    const long long t0 = 3LL;
+    const long long t1 = y * t0;
+    const long long t2 = x * y;
+    const long long t3 = t2 + t1;
+    const long long t4 = 2LL;
+    const long long t5 = 5LL;
+    const long long t6 = y * y;
+    const long long t7 = t4 * t6;
+    const long long t8 = t7 + t5;
+    const long long t9 = x * t3;
+    const long long t10 = t9 + t8;
+    return t10;
+}
+"#
+        );
+
+        // both backends share the typed lowering, so where the Horner scheme has nothing to factor out
+        // they compute the same operations
+        let linear = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(x.clone(), 1)
+            .build()
+            .term_builder(Values::new_scalar(5))
+            .build()
+            .build()
+            .unwrap();
+        let rust = linear
+            .to_rust_fn("model", &[x.clone(), y.clone()], &RustCodegen)
+            .unwrap();
+        let c = linear
+            .to_c("model", &[x.clone(), y.clone()], &CCodegen)
+            .unwrap();
+        let rust_ops = rust
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("let "))
+            .map(|line| line.replace(": i64", ""))
+            .collect::<Vec<_>>();
+        let c_ops = c
+            .source
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("const long long "))
+            .map(|line| line.replace("LL", ""))
+            .collect::<Vec<_>>();
+        assert!(!rust_ops.is_empty());
+        assert_eq!(rust_ops, c_ops);
+
+        let vector = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("u").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            vector.to_c("model", &[provider.get("u").unwrap()], &CCodegen),
+            Err(CodegenError::UnsupportedType(_))
+        ));
+    }
+
+    #[test]
+    fn symbol_ids_test() {
+        let provider = basic_sumbols_provider();
+
+        let labels = provider
+            .symbols()
+            .iter()
+            .map(|symbol| symbol.label.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            ["x", "y", "z", "u", "v", "w", "A", "B", "C", "r", "p", "q"]
+        );
+
+        let x = provider.get("x").unwrap();
+        let a = provider.get("A").unwrap();
+        assert_eq!(x.id().index(), 0);
+        assert_eq!(a.id().index(), 6);
+        // adding an existing label returns the existing symbol
+        assert_eq!(
+            provider.add(SymbolInfo::new_typed("A", Types::Matrix)).id(),
+            a.id()
+        );
+
+        let mut symbols = vec![provider.get("q").unwrap(), a.clone(), x.clone()];
+        symbols.sort();
+        assert_eq!(symbols, [x.clone(), a.clone(), provider.get("q").unwrap()]);
+
+        // the same declarations give the same ids in another provider, but symbols are still different
+        let other = basic_sumbols_provider();
+        let other_x = other.get("x").unwrap();
+        assert_eq!(other_x.id(), x.id());
+        assert_ne!(other_x, x);
+        // then they are ordered by label and by the order their providers were created in
+        let other_y = other.get("y").unwrap();
+        let mut mixed = vec![other_y.clone(), other_x.clone(), x.clone()];
+        mixed.sort();
+        assert_eq!(mixed, [x.clone(), other_x.clone(), other_y]);
+        assert_eq!(
+            HashSet::from([x.clone(), x.clone(), other_x, a.clone()]).len(),
+            3
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        use poly_gnom::serialization::PolynomialSeed;
+        use serde::de::DeserializeSeed;
+
+        let provider = basic_sumbols_provider();
+
+        let sum = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 2)
+            .build()
+            .term_builder(Values::new_scalar(3))
+            .variable(provider.get("y").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let poly = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(2))
+            .polynomial(sum, 2)
+            .variable(provider.get("A").unwrap(), 1)
+            .variable(provider.get("v").unwrap(), 1)
+            .build()
+            .term_builder(Values::new_scalar(-1))
+            .value(Values::new_vector(vec![1, 2]), 1)
+            .variable(provider.get("x").unwrap(), 1)
+            .build()
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&poly).unwrap();
+
+        // unknown symbols are reported unless they are declared from the serialized data
+        let fresh = SymbolsProvider::empty();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert!(MatVecPolynomial::deserialize_into(&mut deserializer, &fresh).is_err());
+        assert!(fresh.symbols().is_empty());
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let restored: MatVecPolynomial = PolynomialSeed::new(&fresh)
+            .declare_missing()
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(restored.to_string(), poly.to_string());
+        assert_eq!(fresh.symbols().len(), 4);
+        assert_eq!(fresh.get("A").unwrap().associated_type, Some(Types::Matrix));
+
+        // into the original provider symbols are linked to the existing ones
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let restored = MatVecPolynomial::deserialize_into(&mut deserializer, &provider).unwrap();
+        let evaluate = |poly: MatVecPolynomial| {
+            poly.substitute_value(provider.get("x").unwrap(), Values::new_scalar(1))
+                .substitute_value(provider.get("y").unwrap(), Values::new_scalar(1))
+                .substitute_value(
+                    provider.get("A").unwrap(),
+                    Values::new_matrix(vec![vec![1, 0], vec![0, 2]]),
+                )
+                .substitute_value(provider.get("v").unwrap(), Values::new_vector(vec![1, 1]))
+                .as_value()
+                .unwrap()
+        };
+        assert_eq!(evaluate(restored), Values::new_vector(vec![31, 62]));
+        assert_eq!(evaluate(poly), Values::new_vector(vec![31, 62]));
+
+        // declared types are checked against the existing symbols
+        let conflicting = SymbolsProvider::empty();
+        conflicting.add(SymbolInfo::new_typed("x", Types::Vector));
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert!(MatVecPolynomial::deserialize_into(&mut deserializer, &conflicting).is_err());
+
+        // symbols of different providers are written side by side, even when their ids collide
+        let other = SymbolsProvider::empty();
+        let s = other.add(SymbolInfo::new_typed("s", Types::Scalar));
+        assert_eq!(s.id(), provider.get("x").unwrap().id());
+        let mixed = MatVecPolynomial::builder()
+            .term_builder(Values::new_scalar(1))
+            .variable(provider.get("x").unwrap(), 1)
+            .variable(s, 1)
+            .build()
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&mixed).unwrap();
+        let target = basic_sumbols_provider();
+        target.add(SymbolInfo::new_typed("s", Types::Scalar));
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let restored = MatVecPolynomial::deserialize_into(&mut deserializer, &target).unwrap();
+        assert_eq!(restored.to_string(), mixed.to_string());
+    }
 }